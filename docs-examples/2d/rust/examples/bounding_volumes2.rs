@@ -24,8 +24,11 @@ fn main() {
         let bounding_sphere_cube1 = cube1.compute_bounding_sphere(&cube1_pos);
         let bounding_sphere_cube2 = cube2.compute_bounding_sphere(&cube2_pos);
 
-        // Merge the two spheres.
-        let bounding_bounding_sphere = bounding_sphere_cube1.merged(&bounding_sphere_cube2);
+        // Merge the two spheres. The merged sphere touches both of them, so it is loosened
+        // by a small margin for the inclusion tests to hold despite rounding errors.
+        let bounding_bounding_sphere = bounding_sphere_cube1
+            .merged(&bounding_sphere_cube2)
+            .loosened(1.0e-5);
 
         // Enlarge the cube2 bounding sphere.
         let loose_bounding_sphere_cube2 = bounding_sphere_cube2.loosened(1.0);
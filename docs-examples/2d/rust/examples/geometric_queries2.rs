@@ -1,11 +1,102 @@
 use approx::relative_eq;
+use parry2d::math::Real;
 use parry2d::na::*;
 use parry2d::query::ClosestPoints;
+use parry2d::query::PointQuery;
+use parry2d::query::Ray;
+use parry2d::query::RayCast;
 use parry2d::query::ShapeCastOptions;
 use parry2d::shape::*;
 use parry2d::*;
+use scenarios::queries;
 
 fn main() {
+    {
+        // DOCUSAURUS: point_projection start
+        let cuboid = Cuboid::new(Vector2::new(1.0, 2.0));
+        let ball = Ball::new(1.0);
+        let capsule = Capsule::new_y(1.0, 0.5);
+        let pos = Isometry2::identity();
+        let point_inside = Point2::origin();
+        let point_outside = Point2::new(2.0, 2.0);
+
+        // Solid projection: a point inside is its own projection.
+        let proj = cuboid.project_point(&pos, &point_inside, true);
+        assert!(proj.is_inside);
+        assert_eq!(proj.point, point_inside);
+        assert_eq!(cuboid.distance_to_point(&pos, &point_inside, true), 0.0);
+
+        // Non-solid projection: a point inside is projected on the border.
+        let proj = cuboid.project_point(&pos, &point_inside, false);
+        assert!(proj.is_inside);
+        assert_eq!(proj.point.coords.norm(), 1.0);
+        assert_eq!(cuboid.distance_to_point(&pos, &point_inside, false), -1.0);
+
+        // The other point is outside of the cuboid so the `solid` flag has no effect.
+        assert!(!cuboid.contains_point(&pos, &point_outside));
+        assert_eq!(cuboid.distance_to_point(&pos, &point_outside, false), 1.0);
+        assert_eq!(cuboid.distance_to_point(&pos, &point_outside, true), 1.0);
+
+        // Every shape supports the same queries.
+        assert!(ball.contains_point(&pos, &point_inside));
+        assert!(capsule.contains_point(&pos, &point_inside));
+        assert!(relative_eq!(
+            ball.distance_to_point(&pos, &point_outside, true),
+            Real::sqrt(8.0) - 1.0,
+            epsilon = 1.0e-6
+        ));
+        assert!(relative_eq!(
+            capsule.distance_to_point(&pos, &point_outside, true),
+            Real::sqrt(5.0) - 0.5,
+            epsilon = 1.0e-6
+        ));
+        // DOCUSAURUS: point_projection stop
+    }
+    {
+        // DOCUSAURUS: ray_cast start
+        let cuboid = Cuboid::new(Vector2::new(1.0, 2.0));
+        let ball = Ball::new(1.0);
+        let pos = Isometry2::identity();
+        let ray_inside = Ray::new(Point2::origin(), Vector2::y());
+        let ray_miss = Ray::new(Point2::new(2.0, 2.0), Vector2::new(1.0, 1.0));
+        assert_eq!(cuboid, queries::ray_cast_cuboid()); // DOCUSAURUS: hide
+        assert_eq!(ray_inside.origin, queries::ray_inside().origin); // DOCUSAURUS: hide
+        assert_eq!(ray_inside.dir, queries::ray_inside().dir); // DOCUSAURUS: hide
+
+        // Solid cast.
+        assert_eq!(
+            cuboid.cast_ray(&pos, &ray_inside, Real::MAX, true),
+            Some(0.0)
+        );
+
+        // Non-solid cast.
+        assert_eq!(
+            cuboid.cast_ray(&pos, &ray_inside, Real::MAX, false),
+            Some(2.0)
+        );
+
+        // The other ray does not intersect this shape.
+        assert!(!cuboid.intersects_ray(&pos, &ray_miss, Real::MAX));
+        assert_eq!(cuboid.cast_ray(&pos, &ray_miss, Real::MAX, false), None);
+        assert_eq!(cuboid.cast_ray(&pos, &ray_miss, Real::MAX, true), None);
+
+        // The normal of the shape where the ray hits it.
+        let ray = Ray::new(Point2::new(-3.0, 0.0), Vector2::x());
+        let hit = cuboid
+            .cast_ray_and_get_normal(&pos, &ray, Real::MAX, true)
+            .unwrap();
+        assert_eq!(hit.time_of_impact, 2.0);
+        assert_eq!(hit.normal, -Vector2::x());
+        // DOCUSAURUS: intersection_point start
+        let intersection_point = ray.origin + ray.dir * hit.time_of_impact;
+        // DOCUSAURUS: intersection_point stop
+        assert_eq!(intersection_point, Point2::new(-1.0, 0.0));
+
+        // `max_time_of_impact` limits how far along the ray shapes are hit.
+        assert!(ball.intersects_ray(&pos, &ray, 2.5));
+        assert!(!ball.intersects_ray(&pos, &ray, 1.5));
+        // DOCUSAURUS: ray_cast stop
+    }
     {
         // DOCUSAURUS: closest_points start
         let cuboid = Cuboid::new(Vector2::new(1.0, 1.0));
@@ -16,6 +107,12 @@ fn main() {
         let ball_pos_intersecting = Isometry2::new(Vector2::new(1.0, 1.0), na::zero());
         let ball_pos_within_margin = Isometry2::new(Vector2::new(2.0, 2.0), na::zero());
         let ball_pos_disjoint = Isometry2::new(Vector2::new(3.0, 3.0), na::zero());
+        assert_eq!(cuboid, queries::cuboid()); // DOCUSAURUS: hide
+        assert_eq!(ball, queries::ball()); // DOCUSAURUS: hide
+        assert_eq!(margin, queries::MARGIN); // DOCUSAURUS: hide
+        assert_eq!(ball_pos_intersecting, queries::ball_positions()[0]); // DOCUSAURUS: hide
+        assert_eq!(ball_pos_within_margin, queries::ball_positions()[1]); // DOCUSAURUS: hide
+        assert_eq!(ball_pos_disjoint, queries::ball_positions()[2]); // DOCUSAURUS: hide
 
         let prox_intersecting =
             query::closest_points(&ball_pos_intersecting, &ball, &cuboid_pos, &cuboid, margin);
@@ -77,8 +174,8 @@ fn main() {
                                         &cuboid_pos,       &cuboid,
                                         prediction);
 
-        assert!(ctct_penetrating.unwrap().unwrap().dist > 0.0);
-        assert!(ctct_in_prediction.unwrap().unwrap().dist < 0.0);
+        assert!(ctct_penetrating.unwrap().unwrap().dist < 0.0);
+        assert!(ctct_in_prediction.unwrap().unwrap().dist > 0.0);
         assert_eq!(ctct_too_far, Ok(None));
         // DOCUSAURUS: contact stop
 
@@ -97,6 +194,16 @@ fn main() {
     
             let ball_vel1 = Vector2::new(2.0, 2.0);
             let ball_vel2 = Vector2::new(-0.5, -0.5);
+            assert_eq!(cuboid, queries::cuboid()); // DOCUSAURUS: hide
+            assert_eq!(ball, queries::ball()); // DOCUSAURUS: hide
+            let [pos1, pos2, pos3] = queries::ball_positions(); // DOCUSAURUS: hide
+            assert_eq!(ball_pos_intersecting, pos1); // DOCUSAURUS: hide
+            assert_eq!(ball_pos_will_touch, pos2); // DOCUSAURUS: hide
+            assert_eq!(ball_pos_wont_touch, pos3); // DOCUSAURUS: hide
+            let [vel1, vel2, vel3] = queries::velocities(); // DOCUSAURUS: hide
+            assert_eq!((ball_vel1, box_vel1), vel1); // DOCUSAURUS: hide
+            assert_eq!((ball_vel2, box_vel2), vel2); // DOCUSAURUS: hide
+            assert_eq!((ball_vel1, box_vel1), vel3); // DOCUSAURUS: hide
     
             let shape_cast_options = ShapeCastOptions::default();
     
@@ -135,6 +242,18 @@ fn main() {
             assert!(result_will_touch.unwrap().time_of_impact > 0.0);
             assert!(result_wont_touch.is_none());
             // DOCUSAURUS: cast_shapes stop
+
+            println!("// DOCUSAURUS: cast_shapes start");
+            println!(
+                "time_of_impact while intersecting: {}",
+                result_intersecting.unwrap().time_of_impact
+            );
+            println!(
+                "time_of_impact before touching: {}",
+                result_will_touch.unwrap().time_of_impact
+            );
+            println!("hit when never touching: {:?}", result_wont_touch);
+            println!("// DOCUSAURUS: cast_shapes stop");
         }
     }
     no_fmt();
@@ -5,13 +5,13 @@ use parry2d::*;
 fn main() {
     {
         // DOCUSAURUS: ball start
-        let ball = Ball::new(1.0f32);
+        let ball = Ball::new(1.0);
         assert!(ball.radius == 1.0);
         // DOCUSAURUS: ball stop
     }
     {
         // DOCUSAURUS: cuboid start
-        let cuboid = Cuboid::new(Vector2::new(2.0f32, 1.0));
+        let cuboid = Cuboid::new(Vector2::new(2.0, 1.0));
 
         assert!(cuboid.half_extents.x == 2.0);
         assert!(cuboid.half_extents.y == 1.0);
@@ -29,7 +29,7 @@ fn main() {
     {
         // DOCUSAURUS: ConvexPolygon start
         let points = [
-            Point2::new(-1.0f32, 1.0),
+            Point2::new(-1.0, 1.0),
             Point2::new(-0.5, -0.5),
             Point2::new(0.0, 0.5),
             Point2::new(0.5, -0.5),
@@ -44,7 +44,7 @@ fn main() {
     {
         // DOCUSAURUS: ConvexPolyline start
         let points = vec![
-            Point2::new(-1.0f32, 1.0),
+            Point2::new(-1.0, 1.0),
             Point2::new(-0.5, -0.5),
             Point2::new(0.5, -0.5),
             Point2::new(1.0, 1.0),
@@ -57,14 +57,14 @@ fn main() {
     {
         // DOCUSAURUS: compound start
         // Delta transformation matrices.
-        let delta1 = Isometry2::new(Vector2::new(0.0f32, -1.5), na::zero());
-        let delta2 = Isometry2::new(Vector2::new(-1.5f32, 0.0), na::zero());
-        let delta3 = Isometry2::new(Vector2::new(1.5f32, 0.0), na::zero());
+        let delta1 = Isometry2::new(Vector2::new(0.0, -1.5), na::zero());
+        let delta2 = Isometry2::new(Vector2::new(-1.5, 0.0), na::zero());
+        let delta3 = Isometry2::new(Vector2::new(1.5, 0.0), na::zero());
 
         // 1) Initialize the shape list.
         let mut shapes = Vec::new();
-        let horizontal_box = SharedShape::new(Cuboid::new(Vector2::new(1.5f32, 0.25)));
-        let vertical_box = SharedShape::new(Cuboid::new(Vector2::new(0.25f32, 1.5)));
+        let horizontal_box = SharedShape::new(Cuboid::new(Vector2::new(1.5, 0.25)));
+        let vertical_box = SharedShape::new(Cuboid::new(Vector2::new(0.25, 1.5)));
 
         shapes.push((delta1, horizontal_box));
         shapes.push((delta2, vertical_box.clone()));
@@ -89,7 +89,7 @@ fn main() {
         // Build the polyline.
         let polyline = Polyline::new(points, None);
 
-        assert!(polyline.vertices().len() == 4);
+        assert!(polyline.vertices().len() == 5);
         // DOCUSAURUS: polyline stop
     }
 }
@@ -1,3 +1,4 @@
+use parry2d::math::Real;
 use parry2d::na::Point2;
 use parry2d::*;
 
@@ -6,7 +7,7 @@ fn main() {
         // DOCUSAURUS: convex_hull start
         let mut points = Vec::new();
         for _ in 0usize..10000 {
-            points.push(rand::random::<Point2<f32>>() * 2.0f32);
+            points.push(rand::random::<Point2<Real>>() * 2.0);
         }
 
         let _convex_hull = transformation::convex_hull(&points[..]);
@@ -1,11 +1,96 @@
 use approx::relative_eq;
+use parry3d::math::Real;
 use parry3d::na::*;
 use parry3d::query::ClosestPoints;
+use parry3d::query::PointQuery;
+use parry3d::query::Ray;
+use parry3d::query::RayCast;
 use parry3d::query::ShapeCastOptions;
 use parry3d::shape::*;
 use parry3d::*;
 
 fn main() {
+    {
+        // DOCUSAURUS: point_projection start
+        let cuboid = Cuboid::new(Vector3::new(1.0, 2.0, 2.0));
+        let ball = Ball::new(1.0);
+        let capsule = Capsule::new_y(1.0, 0.5);
+        let pos = Isometry3::identity();
+        let point_inside = Point3::origin();
+        let point_outside = Point3::new(2.0, 2.0, 2.0);
+
+        // Solid projection: a point inside is its own projection.
+        let proj = cuboid.project_point(&pos, &point_inside, true);
+        assert!(proj.is_inside);
+        assert_eq!(proj.point, point_inside);
+        assert_eq!(cuboid.distance_to_point(&pos, &point_inside, true), 0.0);
+
+        // Non-solid projection: a point inside is projected on the border.
+        let proj = cuboid.project_point(&pos, &point_inside, false);
+        assert!(proj.is_inside);
+        assert_eq!(proj.point.coords.norm(), 1.0);
+        assert_eq!(cuboid.distance_to_point(&pos, &point_inside, false), -1.0);
+
+        // The other point is outside of the cuboid so the `solid` flag has no effect.
+        assert!(!cuboid.contains_point(&pos, &point_outside));
+        assert_eq!(cuboid.distance_to_point(&pos, &point_outside, false), 1.0);
+        assert_eq!(cuboid.distance_to_point(&pos, &point_outside, true), 1.0);
+
+        // Every shape supports the same queries.
+        assert!(ball.contains_point(&pos, &point_inside));
+        assert!(capsule.contains_point(&pos, &point_inside));
+        assert!(relative_eq!(
+            ball.distance_to_point(&pos, &point_outside, true),
+            Real::sqrt(12.0) - 1.0,
+            epsilon = 1.0e-6
+        ));
+        assert!(relative_eq!(
+            capsule.distance_to_point(&pos, &point_outside, true),
+            Real::sqrt(9.0) - 0.5,
+            epsilon = 1.0e-6
+        ));
+        // DOCUSAURUS: point_projection stop
+    }
+    {
+        // DOCUSAURUS: ray_cast start
+        let cuboid = Cuboid::new(Vector3::new(1.0, 2.0, 2.0));
+        let ball = Ball::new(1.0);
+        let pos = Isometry3::identity();
+        let ray_inside = Ray::new(Point3::origin(), Vector3::y());
+        let ray_miss = Ray::new(Point3::new(2.0, 2.0, 2.0), Vector3::new(1.0, 1.0, 1.0));
+
+        // Solid cast.
+        assert_eq!(
+            cuboid.cast_ray(&pos, &ray_inside, Real::MAX, true),
+            Some(0.0)
+        );
+
+        // Non-solid cast.
+        assert_eq!(
+            cuboid.cast_ray(&pos, &ray_inside, Real::MAX, false),
+            Some(2.0)
+        );
+
+        // The other ray does not intersect this shape.
+        assert!(!cuboid.intersects_ray(&pos, &ray_miss, Real::MAX));
+        assert_eq!(cuboid.cast_ray(&pos, &ray_miss, Real::MAX, false), None);
+        assert_eq!(cuboid.cast_ray(&pos, &ray_miss, Real::MAX, true), None);
+
+        // The normal of the shape where the ray hits it.
+        let ray = Ray::new(Point3::new(-3.0, 0.0, 0.0), Vector3::x());
+        let hit = cuboid
+            .cast_ray_and_get_normal(&pos, &ray, Real::MAX, true)
+            .unwrap();
+        assert_eq!(hit.time_of_impact, 2.0);
+        assert_eq!(hit.normal, -Vector3::x());
+        let intersection_point = ray.origin + ray.dir * hit.time_of_impact;
+        assert_eq!(intersection_point, Point3::new(-1.0, 0.0, 0.0));
+
+        // `max_time_of_impact` limits how far along the ray shapes are hit.
+        assert!(ball.intersects_ray(&pos, &ray, 2.5));
+        assert!(!ball.intersects_ray(&pos, &ray, 1.5));
+        // DOCUSAURUS: ray_cast stop
+    }
     {
         // DOCUSAURUS: closest_points start
         let cuboid = Cuboid::new(Vector3::new(1.0, 1.0, 1.0));
@@ -73,8 +158,8 @@ fn main() {
                                             &cuboid_pos,       &cuboid,
                                             prediction);
 
-            assert!(ctct_penetrating.unwrap().unwrap().dist > 0.0);
-            assert!(ctct_in_prediction.unwrap().unwrap().dist < 0.0);
+            assert!(ctct_penetrating.unwrap().unwrap().dist < 0.0);
+            assert!(ctct_in_prediction.unwrap().unwrap().dist > 0.0);
             assert_eq!(ctct_too_far, Ok(None));
             // DOCUSAURUS: contact stop
         }
@@ -107,6 +192,18 @@ fn main() {
             assert!(result_will_touch.unwrap().time_of_impact > 0.0);
             assert!(result_wont_touch.is_none());
             // DOCUSAURUS: cast_shapes stop
+
+            println!("// DOCUSAURUS: cast_shapes start");
+            println!(
+                "time_of_impact while intersecting: {}",
+                result_intersecting.unwrap().time_of_impact
+            );
+            println!(
+                "time_of_impact before touching: {}",
+                result_will_touch.unwrap().time_of_impact
+            );
+            println!("hit when never touching: {:?}", result_wont_touch);
+            println!("// DOCUSAURUS: cast_shapes stop");
         }
     }
     no_fmt();
@@ -3,17 +3,20 @@ use parry3d::na::Point3;
 use parry3d::na::Vector3;
 use parry3d::shape::*;
 use parry3d::*;
+use scenarios::representations;
 
 fn main() {
     {
         // DOCUSAURUS: ball start
-        let ball = Ball::new(1.0f32);
+        let ball = Ball::new(1.0);
+        assert_eq!(ball, representations::ball()); // DOCUSAURUS: hide
         assert!(ball.radius == 1.0);
         // DOCUSAURUS: ball stop
     }
     {
         // DOCUSAURUS: cuboid start
-        let cuboid = Cuboid::new(Vector3::new(2.0f32, 1.0, 3.0));
+        let cuboid = Cuboid::new(Vector3::new(2.0, 1.0, 3.0));
+        assert_eq!(cuboid, representations::cuboid()); // DOCUSAURUS: hide
 
         assert!(cuboid.half_extents.x == 2.0);
         assert!(cuboid.half_extents.y == 1.0);
@@ -23,7 +26,8 @@ fn main() {
 
     {
         // DOCUSAURUS: cylinder start
-        let cylinder = Cylinder::new(0.5f32, 1.0);
+        let cylinder = Cylinder::new(0.5, 1.0);
+        assert_eq!(cylinder, representations::cylinder()); // DOCUSAURUS: hide
 
         assert!(cylinder.half_height == 0.5);
         assert!(cylinder.radius == 1.0);
@@ -31,7 +35,8 @@ fn main() {
     }
     {
         // DOCUSAURUS: cone start
-        let cone = Cone::new(0.5f32, 0.75);
+        let cone = Cone::new(0.5, 0.75);
+        assert_eq!(cone, representations::cone()); // DOCUSAURUS: hide
 
         assert!(cone.half_height == 0.5);
         assert!(cone.radius == 0.75);
@@ -40,6 +45,8 @@ fn main() {
     {
         // DOCUSAURUS: capsule start
         let capsule = Capsule::new(Point3::new(0.0, 0.5, 0.0), Point3::new(0.0, 1.0, 0.0), 0.75);
+        assert_eq!(capsule.segment, representations::capsule().segment); // DOCUSAURUS: hide
+        assert_eq!(capsule.radius, representations::capsule().radius); // DOCUSAURUS: hide
 
         assert!(capsule.segment.a == Point3::new(0.0, 0.5, 0.0));
         assert!(capsule.segment.b == Point3::new(0.0, 1.0, 0.0));
@@ -51,7 +58,7 @@ fn main() {
         {
             // DOCUSAURUS: ConvexPolyhedron start
             let points = [
-                Point3::new(0.0f32, 0.0, 1.0),
+                Point3::new(0.0, 0.0, 1.0),
                 Point3::new(0.0, 0.0, -1.0),
                 Point3::new(0.0, 1.0, 0.0),
                 Point3::new(0.0, -1.0, 0.0),
@@ -61,13 +68,14 @@ fn main() {
             ];
             
             let convex = ConvexPolyhedron::from_convex_hull(&points).expect("Convex hull computation failed.");
+            assert_eq!(convex, representations::convex()); // DOCUSAURUS: hide
             assert!(convex.points().len() == 6); // The convex hull has only 6 vertices.
             // DOCUSAURUS: ConvexPolyhedron stop
         }
         {
             // DOCUSAURUS: ConvexMesh start
             let points = vec![
-                Point3::new(0.0f32, 0.0, 1.0),
+                Point3::new(0.0, 0.0, 1.0),
                 Point3::new(0.0, 0.0, -1.0),
                 Point3::new(0.0, 1.0, 0.0),
                 Point3::new(0.0, -1.0, 0.0),
@@ -89,14 +97,14 @@ fn main() {
         use parry3d::shape::Compound;
         // DOCUSAURUS: compound start
         // Delta transformation matrices.
-        let delta1 = Isometry3::new(Vector3::new(0.0f32, -1.5, 0.0), na::zero());
-        let delta2 = Isometry3::new(Vector3::new(-1.5f32, 0.0, 0.0), na::zero());
-        let delta3 = Isometry3::new(Vector3::new(1.5f32, 0.0, 0.0), na::zero());
+        let delta1 = Isometry3::new(Vector3::new(0.0, -1.5, 0.0), na::zero());
+        let delta2 = Isometry3::new(Vector3::new(-1.5, 0.0, 0.0), na::zero());
+        let delta3 = Isometry3::new(Vector3::new(1.5, 0.0, 0.0), na::zero());
 
         // 1) Initialize the shape list.
         let mut shapes = Vec::new();
-        let horizontal_box = SharedShape::new(Cuboid::new(Vector3::new(1.5f32, 0.25, 0.25)));
-        let vertical_box = SharedShape::new(Cuboid::new(Vector3::new(0.25f32, 1.5, 0.25)));
+        let horizontal_box = SharedShape::new(Cuboid::new(Vector3::new(1.5, 0.25, 0.25)));
+        let vertical_box = SharedShape::new(Cuboid::new(Vector3::new(0.25, 1.5, 0.25)));
 
         shapes.push((delta1, horizontal_box));
         shapes.push((delta2, vertical_box.clone()));
@@ -104,6 +112,8 @@ fn main() {
 
         // 2) Create the compound shape.
         let compound = Compound::new(shapes);
+        let expected = representations::cuboids(&representations::compound()); // DOCUSAURUS: hide
+        assert_eq!(representations::cuboids(&compound), expected); // DOCUSAURUS: hide
 
         assert!(compound.shapes().len() == 3)
         // DOCUSAURUS: compound stop
@@ -138,6 +148,9 @@ fn main() {
 
         // Build the mesh.
         let mesh = TriMesh::new(points, indices);
+        let expected = representations::trimesh(); // DOCUSAURUS: hide
+        assert_eq!(mesh.vertices(), expected.vertices()); // DOCUSAURUS: hide
+        assert_eq!(mesh.indices(), expected.indices()); // DOCUSAURUS: hide
 
         assert!(mesh.vertices().len() == 4);
         // DOCUSAURUS: trimesh stop
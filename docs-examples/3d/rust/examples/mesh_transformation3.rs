@@ -1,3 +1,4 @@
+use parry3d::math::Real;
 use parry3d::na::Point3;
 use parry3d::*;
 
@@ -6,7 +7,7 @@ fn main() {
         // DOCUSAURUS: convex_hull start
         let mut points = Vec::new();
         for _ in 0usize..10000 {
-            points.push(rand::random::<Point3<f32>>() * 2.0f32);
+            points.push(rand::random::<Point3<Real>>() * 2.0);
         }
 
         let _convex_hull = transformation::convex_hull(&points[..]);
@@ -0,0 +1,241 @@
+//! The figures of the user guide, drawn from the [`queries`] scenarios of the examples.
+//!
+//! Each figure asserts what it shows, like the examples do, so that a figure cannot be
+//! generated from a scenario that parry no longer computes the same way.
+
+use crate::{Style, Svg};
+use parry2d::bounding_volume::Aabb;
+use parry2d::math::{Isometry, Point, Real};
+use parry2d::na::{self, UnitComplex};
+use parry2d::query::{self, ClosestPoints, PointQuery, RayCast, ShapeCastOptions};
+use parry2d::shape::{ConvexPolygon, Cuboid, Polyline, RoundShape, Shape};
+use scenarios::queries;
+
+/// Horizontal distance between the panels of a figure.
+const PANEL_SPACING: Real = 7.0;
+
+const SHAPE: Style = Style::filled("black", "#d9e6f2");
+const OTHER_SHAPE: Style = Style::filled("black", "#f2e0c9");
+const BOUNDING_VOLUME: Style = Style::stroke("#c0392b");
+const QUERY: Style = Style::stroke("#2c7a3f");
+const TEXT: Style = Style::stroke("black");
+
+/// Every figure, with the name of its file in `static/img`.
+pub fn figures() -> Vec<(&'static str, Svg)> {
+    vec![
+        ("bounding_volumes.svg", bounding_volumes()),
+        ("proximity.svg", proximity()),
+        ("solid_ray_cast.svg", solid_ray_cast()),
+        ("time_of_impact.svg", time_of_impact()),
+    ]
+}
+
+/// A concave polygon bounded by its bounding sphere, its AABB, its OBB and its convex hull.
+pub fn bounding_volumes() -> Svg {
+    let vertices = vec![
+        Point::new(-2.0, -1.0),
+        Point::new(1.5, -1.5),
+        Point::new(2.0, 0.5),
+        Point::new(0.0, 0.0),
+        Point::new(-1.0, 1.5),
+    ];
+    let indices = (0..vertices.len() as u32)
+        .map(|i| [i, (i + 1) % vertices.len() as u32])
+        .collect();
+    let polygon = Polyline::new(vertices.clone(), Some(indices));
+    let hull = ConvexPolygon::from_convex_hull(&vertices).unwrap();
+
+    let mut svg = Svg::default();
+    for (i, title) in ["Bounding sphere", "AABB", "OBB", "Convex hull"]
+        .into_iter()
+        .enumerate()
+    {
+        let pos = panel(i);
+        svg.shape(&polygon, &pos, SHAPE);
+        match i {
+            0 => {
+                let sphere = polygon.compute_bounding_sphere(&pos);
+                assert!(vertices
+                    .iter()
+                    .all(|vertex| na::distance(&sphere.center, &(pos * vertex)) <= sphere.radius));
+                svg.bounding_sphere(&sphere, BOUNDING_VOLUME);
+            }
+            1 => {
+                let aabb = polygon.compute_aabb(&pos);
+                assert!(vertices
+                    .iter()
+                    .all(|vertex| aabb.contains_local_point(&(pos * vertex))));
+                svg.aabb(&aabb, BOUNDING_VOLUME);
+            }
+            2 => {
+                let (obb, obb_pos) = smallest_obb(&hull);
+                let obb_pos = pos * obb_pos;
+                assert!(vertices.iter().all(|vertex| obb.distance_to_point(
+                    &obb_pos,
+                    &(pos * vertex),
+                    true
+                ) <= 1.0e-5));
+                svg.shape(&obb, &obb_pos, BOUNDING_VOLUME);
+            }
+            _ => {
+                assert!(vertices
+                    .iter()
+                    .all(|vertex| hull.distance_to_local_point(vertex, true) <= 1.0e-5));
+                svg.shape(&hull, &pos, BOUNDING_VOLUME);
+            }
+        }
+        svg.label(&(pos * Point::new(0.0, -3.0)), title, TEXT);
+    }
+    svg
+}
+
+/// A ball intersecting a cuboid, within the margin around it, and disjoint from it, as in the
+/// `closest_points` region of `geometric_queries2.rs`.
+pub fn proximity() -> Svg {
+    let cuboid = queries::cuboid();
+    let ball = queries::ball();
+    let margin_outline = RoundShape {
+        inner_shape: cuboid,
+        border_radius: queries::MARGIN,
+    };
+
+    let mut svg = Svg::default();
+    let titles = ["Intersecting", "Within margin", "Disjoint"];
+    let positions = titles.into_iter().zip(queries::ball_positions());
+    for (i, (title, ball_pos)) in positions.enumerate() {
+        let cuboid_pos = panel(i);
+        let ball_pos = cuboid_pos * ball_pos;
+        let proximity =
+            query::closest_points(&ball_pos, &ball, &cuboid_pos, &cuboid, queries::MARGIN);
+        match (i, proximity) {
+            (0, Ok(ClosestPoints::Intersecting)) => {}
+            (1, Ok(ClosestPoints::WithinMargin(point1, point2))) => {
+                svg.segment(&point1, &point2, QUERY)
+                    .point(&point1, QUERY)
+                    .point(&point2, QUERY);
+            }
+            (2, Ok(ClosestPoints::Disjoint)) => {}
+            (_, proximity) => panic!("Unexpected proximity for {title}: {proximity:?}"),
+        }
+        svg.shape(&cuboid, &cuboid_pos, SHAPE)
+            .shape(&margin_outline, &cuboid_pos, BOUNDING_VOLUME.dashed())
+            .shape(&ball, &ball_pos, OTHER_SHAPE)
+            .label(&(cuboid_pos * Point::new(1.0, -2.0)), title, TEXT);
+    }
+    svg
+}
+
+/// A ray starting inside of a cuboid, cast with and without `solid`, as in the `ray_cast`
+/// region of `geometric_queries2.rs`.
+pub fn solid_ray_cast() -> Svg {
+    let cuboid = queries::ray_cast_cuboid();
+
+    let mut svg = Svg::default();
+    for (i, solid) in [true, false].into_iter().enumerate() {
+        let pos = panel(i);
+        let ray = queries::ray_inside().transform_by(&pos);
+        let hit = cuboid
+            .cast_ray_and_get_normal(&pos, &ray, Real::MAX, solid)
+            .unwrap();
+        assert_eq!(hit.time_of_impact, if solid { 0.0 } else { 2.0 });
+
+        let hit_point = ray.point_at(hit.time_of_impact);
+        svg.shape(&cuboid, &pos, SHAPE)
+            .ray(&ray, 3.0, QUERY.dashed())
+            .point(&ray.origin, TEXT)
+            .point(&hit_point, QUERY);
+        if !solid {
+            let normal_end = hit_point + hit.normal;
+            svg.arrow(&hit_point, &normal_end, BOUNDING_VOLUME);
+        }
+        svg.label(
+            &(pos * Point::new(0.0, -3.0)),
+            &format!("solid: {solid}, time_of_impact: {}", hit.time_of_impact),
+            TEXT,
+        );
+    }
+    svg
+}
+
+/// A ball and a cuboid touching at time 0, touching later, and never touching, as in the
+/// `cast_shapes` region of `geometric_queries2.rs`.
+///
+/// The shapes are drawn at their initial positions with their velocities, and dashed where
+/// they start touching.
+pub fn time_of_impact() -> Svg {
+    let cuboid = queries::cuboid();
+    let ball = queries::ball();
+
+    let mut svg = Svg::default();
+    let motions = queries::ball_positions()
+        .into_iter()
+        .zip(queries::velocities());
+    for (i, (ball_pos, (ball_vel, box_vel))) in motions.enumerate() {
+        let cuboid_pos = panel(i);
+        let ball_pos = cuboid_pos * ball_pos;
+        let hit = query::cast_shapes(
+            &ball_pos,
+            &ball_vel,
+            &ball,
+            &cuboid_pos,
+            &box_vel,
+            &cuboid,
+            ShapeCastOptions::default(),
+        )
+        .unwrap();
+
+        svg.shape(&cuboid, &cuboid_pos, SHAPE)
+            .shape(&ball, &ball_pos, OTHER_SHAPE);
+        let title = match (i, hit) {
+            (0, Some(hit)) if hit.time_of_impact == 0.0 => "time_of_impact: 0".to_string(),
+            (1, Some(hit)) if hit.time_of_impact > 0.0 => {
+                let toi = hit.time_of_impact;
+                let ball_pos_at_toi = Isometry::new(ball_vel * toi, 0.0) * ball_pos;
+                let cuboid_pos_at_toi = Isometry::new(box_vel * toi, 0.0) * cuboid_pos;
+                svg.shape(&cuboid, &cuboid_pos_at_toi, SHAPE.dashed())
+                    .shape(&ball, &ball_pos_at_toi, OTHER_SHAPE.dashed())
+                    .point(&(ball_pos_at_toi * hit.witness1), QUERY);
+                format!("time_of_impact: {toi:.2}")
+            }
+            (2, None) => "no impact".to_string(),
+            (_, hit) => panic!("Unexpected shape cast hit in panel {i}: {hit:?}"),
+        };
+        let ball_center = ball_pos * Point::origin();
+        let cuboid_center = cuboid_pos * Point::origin();
+        svg.arrow(&ball_center, &(ball_center + ball_vel), QUERY)
+            .arrow(&cuboid_center, &(cuboid_center + box_vel), QUERY)
+            .label(&(cuboid_pos * Point::new(1.0, -2.0)), &title, TEXT);
+    }
+    svg
+}
+
+/// The oriented bounding box of `hull` with the smallest area, as a cuboid and its position.
+///
+/// Parry has no OBB. One side of the smallest one lies on an edge of the convex hull, so the
+/// box aligned with each edge is tried.
+fn smallest_obb(hull: &ConvexPolygon) -> (Cuboid, Isometry<Real>) {
+    let points = hull.points();
+    (0..points.len())
+        .map(|i| {
+            let edge = points[(i + 1) % points.len()] - points[i];
+            let rotation = UnitComplex::new(edge.y.atan2(edge.x));
+            let aligned = points
+                .iter()
+                .map(|point| rotation.inverse_transform_point(point))
+                .collect::<Vec<_>>();
+            let aabb = Aabb::from_points(&aligned);
+            let pos = Isometry::from_parts((rotation * aabb.center().coords).into(), rotation);
+            (Cuboid::new(aabb.half_extents()), pos)
+        })
+        .min_by(|(a, _), (b, _)| {
+            a.half_extents
+                .product()
+                .total_cmp(&b.half_extents.product())
+        })
+        .unwrap()
+}
+
+/// The position of the `i`-th panel of a figure, from left to right.
+fn panel(i: usize) -> Isometry<Real> {
+    Isometry::translation(i as Real * PANEL_SPACING, 0.0)
+}
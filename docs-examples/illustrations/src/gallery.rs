@@ -0,0 +1,73 @@
+//! The 3D shapes of the user guide, the [`representations`] of
+//! `3d/rust/examples/geometric_representations3.rs`, discretized into triangle meshes.
+
+use parry3d::math::{Isometry, Point, Real};
+use parry3d::shape::{Shape, SharedShape};
+use scenarios::representations;
+
+/// Number of subdivisions around the axis of round shapes.
+const THETA_SUBDIVISIONS: u32 = 48;
+/// Number of subdivisions from pole to pole of balls and capsules.
+const PHI_SUBDIVISIONS: u32 = 24;
+
+/// A triangle mesh, as returned by the `to_trimesh` methods of parry.
+pub type Mesh = (Vec<Point<Real>>, Vec<[u32; 3]>);
+
+/// Every shape of the gallery, with the name of its image in `static/img`, e.g. `ball3d`.
+pub fn gallery() -> Vec<(&'static str, Mesh)> {
+    let shapes = vec![
+        ("ball3d", SharedShape::new(representations::ball())),
+        ("cuboid3d", SharedShape::new(representations::cuboid())),
+        ("cylinder3d", SharedShape::new(representations::cylinder())),
+        ("cone3d", SharedShape::new(representations::cone())),
+        ("capsule3d", SharedShape::new(representations::capsule())),
+        ("convex3d", SharedShape::new(representations::convex())),
+        ("compound3d", SharedShape::new(representations::compound())),
+        ("mesh3d", SharedShape::new(representations::trimesh())),
+    ];
+    shapes
+        .into_iter()
+        .map(|(name, shape)| (name, to_trimesh(&*shape, &Isometry::identity())))
+        .collect()
+}
+
+/// The triangle mesh of `shape` at the position `pos`.
+///
+/// # Panics
+///
+/// If the shape is not a ball, cuboid, cylinder, cone, capsule, convex polyhedron, triangle
+/// mesh or compound of those.
+pub fn to_trimesh(shape: &dyn Shape, pos: &Isometry<Real>) -> Mesh {
+    let (vertices, indices) = if let Some(ball) = shape.as_ball() {
+        ball.to_trimesh(THETA_SUBDIVISIONS, PHI_SUBDIVISIONS)
+    } else if let Some(cuboid) = shape.as_cuboid() {
+        cuboid.to_trimesh()
+    } else if let Some(cylinder) = shape.as_cylinder() {
+        cylinder.to_trimesh(THETA_SUBDIVISIONS)
+    } else if let Some(cone) = shape.as_cone() {
+        cone.to_trimesh(THETA_SUBDIVISIONS)
+    } else if let Some(capsule) = shape.as_capsule() {
+        capsule.to_trimesh(THETA_SUBDIVISIONS, PHI_SUBDIVISIONS)
+    } else if let Some(convex) = shape.as_convex_polyhedron() {
+        convex.to_trimesh()
+    } else if let Some(trimesh) = shape.as_trimesh() {
+        (trimesh.vertices().to_vec(), trimesh.indices().to_vec())
+    } else if let Some(compound) = shape.as_compound() {
+        let mut merged: Mesh = (Vec::new(), Vec::new());
+        for (part_pos, part) in compound.shapes() {
+            let (vertices, indices) = to_trimesh(&**part, &(pos * part_pos));
+            let offset = merged.0.len() as u32;
+            merged.0.extend(vertices);
+            merged
+                .1
+                .extend(indices.iter().map(|idx| idx.map(|i| i + offset)));
+        }
+        return merged;
+    } else {
+        panic!("Cannot discretize shapes of type {:?}", shape.shape_type())
+    };
+    (
+        vertices.iter().map(|vertex| pos * vertex).collect(),
+        indices,
+    )
+}
@@ -0,0 +1,16 @@
+//! Headless rendering of the images of the user guide from code: parry2d shapes and query
+//! results to SVG, to draw the figures from the scenarios the examples assert on, and parry3d
+//! shapes to OBJ and PNG, to draw the gallery of 3D shapes without a GPU.
+//!
+//! The `illustrations` binary writes every figure of [`figures::figures`] to a directory, e.g.
+//! `static/img`, and `illustrations gallery` the image of every shape of [`gallery::gallery`],
+//! and its OBJ file to an optional second directory. With `--check`, the figures and images
+//! are compared with the existing files instead, the images with [`raster::same_image`].
+
+pub mod figures;
+pub mod gallery;
+pub mod obj;
+pub mod raster;
+mod svg;
+
+pub use svg::{Style, Svg};
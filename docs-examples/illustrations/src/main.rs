@@ -0,0 +1,78 @@
+use illustrations::raster::{render_png, same_image};
+use illustrations::{figures::figures, gallery::gallery, obj::to_obj};
+use std::{fs, path::Path, process::ExitCode};
+
+/// The size of the images of the gallery, in pixels, like the screenshots they replace.
+const GALLERY_IMAGE_SIZE: u32 = 200;
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut paths = Vec::new();
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            _ => paths.push(arg),
+        }
+    }
+
+    let mut out_of_date = 0;
+    if paths.first().map(String::as_str) == Some("gallery") {
+        let (output_dir, obj_dir) = match &paths[..] {
+            [_, output_dir] => (output_dir, None),
+            [_, output_dir, obj_dir] => (output_dir, Some(Path::new(obj_dir))),
+            _ => panic!(
+                "Expected 1 or 2 arguments with gallery: the output directory of the images, \
+                 e.g. static/img, and the one of the OBJ files, e.g. target/gallery."
+            ),
+        };
+        for (name, mesh) in gallery() {
+            let png = render_png(&mesh, GALLERY_IMAGE_SIZE);
+            let path = Path::new(output_dir).join(format!("{name}.png"));
+            out_of_date += update(&path, &png, check, same_image);
+            // The OBJ files are only generated, for other renderers: they are not committed.
+            if let (Some(obj_dir), false) = (obj_dir, check) {
+                fs::create_dir_all(obj_dir).unwrap_or_else(|_| {
+                    panic!("Could not create output directory {}", obj_dir.display())
+                });
+                let path = obj_dir.join(format!("{name}.obj"));
+                update(&path, to_obj(&mesh).as_bytes(), false, <[u8]>::eq);
+            }
+        }
+    } else {
+        let [output_dir] = &paths[..] else {
+            panic!("Expected 1 argument: the output directory, e.g. static/img.");
+        };
+        for (name, svg) in figures() {
+            let path = Path::new(output_dir).join(name);
+            out_of_date += update(&path, svg.render().as_bytes(), check, <[u8]>::eq);
+        }
+    }
+
+    if out_of_date > 0 {
+        eprintln!(
+            "❌ {out_of_date} file(s) are not up to date, run without --check to regenerate them."
+        );
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Writes `content` to `path`, or if `check` is set, compares them with `same`.
+///
+/// Returns 1 if `path` is out of date, 0 otherwise.
+fn update(path: &Path, content: &[u8], check: bool, same: fn(&[u8], &[u8]) -> bool) -> usize {
+    if check {
+        if !fs::read(path).is_ok_and(|existing| same(&existing, content)) {
+            eprintln!(
+                "❌ ERROR: {}: not up to date with its scenario",
+                path.display()
+            );
+            return 1;
+        }
+    } else {
+        fs::write(path, content)
+            .unwrap_or_else(|_| panic!("Could not write output file {}", path.display()));
+        println!("🔄 Regenerated {}", path.display());
+    }
+    0
+}
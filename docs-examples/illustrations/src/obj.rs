@@ -0,0 +1,17 @@
+//! Export of triangle meshes to the Wavefront OBJ format.
+
+use crate::gallery::Mesh;
+
+/// The OBJ file of `mesh`, with one `v` line per vertex and one `f` line per triangle.
+pub fn to_obj(mesh: &Mesh) -> String {
+    let (vertices, indices) = mesh;
+    let mut obj = String::new();
+    for vertex in vertices {
+        obj.push_str(&format!("v {} {} {}\n", vertex.x, vertex.y, vertex.z));
+    }
+    // OBJ indices start at 1.
+    for [a, b, c] in indices {
+        obj.push_str(&format!("f {} {} {}\n", a + 1, b + 1, c + 1));
+    }
+    obj
+}
@@ -0,0 +1,222 @@
+//! A CPU rasterizer rendering triangle meshes to PNG images, without a GPU or a window.
+//!
+//! The camera looks at the mesh from the front, slightly above and to the right, and frames
+//! its bounding sphere. Triangles are flat shaded by a directional light, and the image is
+//! rendered at twice its size then downsampled to smooth the edges.
+
+use crate::gallery::Mesh;
+use parry3d::math::{Point, Real, Vector};
+use parry3d::na::{self, Isometry3, Perspective3};
+
+/// Each pixel of the image averages `SUPERSAMPLING²` rendered samples.
+const SUPERSAMPLING: usize = 2;
+/// The vertical field of view of the camera, in radians.
+const FIELD_OF_VIEW: Real = std::f64::consts::FRAC_PI_4 as Real;
+/// The direction from the mesh to the camera.
+const EYE_DIRECTION: [Real; 3] = [0.5, 0.6, 1.0];
+/// The direction from the mesh to the light.
+const LIGHT_DIRECTION: [Real; 3] = [-0.3, 0.8, 0.6];
+/// The color of the fully lit mesh.
+const COLOR: [Real; 3] = [30.0, 110.0, 255.0];
+/// The color of the background.
+const BACKGROUND: [u8; 3] = [255, 255, 255];
+/// The share of the color lit whatever the orientation of a triangle.
+const AMBIENT: Real = 0.35;
+
+/// The largest difference of a color channel between two pixels that look the same.
+pub const CHANNEL_TOLERANCE: u8 = 16;
+/// The largest share of the pixels of two images that may differ for the images to look the
+/// same.
+pub const PIXEL_TOLERANCE: f32 = 0.01;
+
+/// Renders `mesh` to a square PNG image of `size` pixels.
+pub fn render_png(mesh: &Mesh, size: u32) -> Vec<u8> {
+    let pixels = downsample(
+        &rasterize(mesh, size as usize * SUPERSAMPLING),
+        size as usize,
+    );
+    encode_png(&pixels, size)
+}
+
+/// The square PNG image of `size` pixels with the RGB bytes `pixels`, row by row from the top.
+pub fn encode_png(pixels: &[u8], size: u32) -> Vec<u8> {
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, size, size);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .expect("Could not write the PNG header.");
+    writer
+        .write_image_data(pixels)
+        .expect("Could not write the PNG image.");
+    writer.finish().expect("Could not write the PNG image.");
+    png
+}
+
+/// The width, the height and the RGB pixels of the PNG image `png`, row by row from the top,
+/// or `None` if it is not an 8-bit RGB PNG image.
+pub fn decode_png(png: &[u8]) -> Option<(u32, u32, Vec<[u8; 3]>)> {
+    let mut reader = png::Decoder::new(png).read_info().ok()?;
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut pixels).ok()?;
+    if (info.color_type, info.bit_depth) != (png::ColorType::Rgb, png::BitDepth::Eight) {
+        return None;
+    }
+    let pixels = pixels[..info.buffer_size()]
+        .chunks(3)
+        .map(|pixel| [pixel[0], pixel[1], pixel[2]])
+        .collect();
+    Some((info.width, info.height, pixels))
+}
+
+/// Whether the PNG images `png1` and `png2` look the same: they have the same size, and at
+/// most [`PIXEL_TOLERANCE`] of their pixels differ by more than [`CHANNEL_TOLERANCE`] in a
+/// channel.
+///
+/// Rendering the same mesh on another platform may round a few colors or edges differently,
+/// which comparing the bytes of the images would report.
+pub fn same_image(png1: &[u8], png2: &[u8]) -> bool {
+    let (Some((width1, height1, pixels1)), Some((width2, height2, pixels2))) =
+        (decode_png(png1), decode_png(png2))
+    else {
+        return false;
+    };
+    if (width1, height1) != (width2, height2) {
+        return false;
+    }
+    let different = pixels1
+        .iter()
+        .zip(&pixels2)
+        .filter(|(pixel1, pixel2)| {
+            pixel1
+                .iter()
+                .zip(pixel2.iter())
+                .any(|(channel1, channel2)| channel1.abs_diff(*channel2) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    different as f32 <= PIXEL_TOLERANCE * pixels1.len() as f32
+}
+
+/// The RGB colors of the `size` × `size` pixels of `mesh`, row by row from the top.
+fn rasterize(mesh: &Mesh, size: usize) -> Vec<[Real; 3]> {
+    let background = BACKGROUND.map(Real::from);
+    let mut colors = vec![background; size * size];
+    let (vertices, indices) = mesh;
+    if vertices.is_empty() {
+        return colors;
+    }
+
+    // Frame the bounding sphere of the mesh.
+    let (mut mins, mut maxs) = (vertices[0], vertices[0]);
+    for vertex in vertices {
+        mins = mins.inf(vertex);
+        maxs = maxs.sup(vertex);
+    }
+    let center = na::center(&mins, &maxs);
+    let radius = vertices
+        .iter()
+        .map(|vertex| (vertex - center).norm())
+        .fold(0.0, Real::max)
+        .max(Real::EPSILON);
+    let distance = radius / (FIELD_OF_VIEW / 2.0).sin() * 1.1;
+    let eye_direction = Vector::from(EYE_DIRECTION).normalize();
+    let eye = center + eye_direction * distance;
+    let view = Isometry3::look_at_rh(&eye, &center, &Vector::y());
+    let projection = Perspective3::new(
+        1.0,
+        FIELD_OF_VIEW,
+        (distance - radius) * 0.5,
+        distance + radius * 2.0,
+    );
+    let light = Vector::from(LIGHT_DIRECTION).normalize();
+
+    // The pixel coordinates and depth of each vertex.
+    let screen = vertices
+        .iter()
+        .map(|vertex| {
+            let ndc = projection.project_point(&(view * vertex));
+            Point::new(
+                (ndc.x + 1.0) / 2.0 * size as Real,
+                (1.0 - ndc.y) / 2.0 * size as Real,
+                ndc.z,
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut depths = vec![Real::MAX; size * size];
+
+    for [a, b, c] in indices {
+        let (a, b, c) = (*a as usize, *b as usize, *c as usize);
+        let Some(mut normal) = (vertices[b] - vertices[a])
+            .cross(&(vertices[c] - vertices[a]))
+            .try_normalize(Real::EPSILON)
+        else {
+            continue;
+        };
+        // Light both sides of the triangles, whatever their orientation.
+        if normal.dot(&(eye - vertices[a])) < 0.0 {
+            normal = -normal;
+        }
+        let intensity = AMBIENT + (1.0 - AMBIENT) * normal.dot(&light).max(0.0);
+        let color = COLOR.map(|channel| channel * intensity);
+
+        let (pa, pb, pc) = (screen[a], screen[b], screen[c]);
+        let area = edge(&pa, &pb, &pc);
+        if area.abs() <= Real::EPSILON {
+            continue;
+        }
+        let min_x = pa.x.min(pb.x).min(pc.x).floor().max(0.0) as usize;
+        let max_x = (pa.x.max(pb.x).max(pc.x).ceil() as usize).min(size);
+        let min_y = pa.y.min(pb.y).min(pc.y).floor().max(0.0) as usize;
+        let max_y = (pa.y.max(pb.y).max(pc.y).ceil() as usize).min(size);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = Point::new(x as Real + 0.5, y as Real + 0.5, 0.0);
+                let (wa, wb, wc) = (
+                    edge(&pb, &pc, &p) / area,
+                    edge(&pc, &pa, &p) / area,
+                    edge(&pa, &pb, &p) / area,
+                );
+                if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                    continue;
+                }
+                let depth = wa * pa.z + wb * pb.z + wc * pc.z;
+                let i = y * size + x;
+                if depth < depths[i] {
+                    depths[i] = depth;
+                    colors[i] = color;
+                }
+            }
+        }
+    }
+    colors
+}
+
+/// The RGB bytes of the `size` × `size` image averaging the blocks of `SUPERSAMPLING²` samples
+/// of `colors`.
+fn downsample(colors: &[[Real; 3]], size: usize) -> Vec<u8> {
+    let samples_size = size * SUPERSAMPLING;
+    let mut pixels = Vec::with_capacity(size * size * 3);
+    for y in 0..size {
+        for x in 0..size {
+            let mut sum = [0.0; 3];
+            for dy in 0..SUPERSAMPLING {
+                for dx in 0..SUPERSAMPLING {
+                    let sample =
+                        colors[(y * SUPERSAMPLING + dy) * samples_size + x * SUPERSAMPLING + dx];
+                    for (sum, channel) in sum.iter_mut().zip(sample) {
+                        *sum += channel;
+                    }
+                }
+            }
+            let count = (SUPERSAMPLING * SUPERSAMPLING) as Real;
+            pixels.extend(sum.map(|sum| (sum / count).round().clamp(0.0, 255.0) as u8));
+        }
+    }
+    pixels
+}
+
+/// Twice the signed area of the triangle `a`, `b`, `p`, in the plane of the image.
+fn edge(a: &Point<Real>, b: &Point<Real>, p: &Point<Real>) -> Real {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
@@ -0,0 +1,323 @@
+//! An SVG canvas drawing parry2d shapes, bounding volumes and query results.
+
+use parry2d::bounding_volume::{Aabb, BoundingSphere};
+use parry2d::math::{Isometry, Point, Real, Vector};
+use parry2d::query::{Contact, Ray};
+use parry2d::shape::Shape;
+use std::{fs, io, path::Path};
+
+/// Pixels per unit of length of the scene.
+const DEFAULT_SCALE: Real = 40.0;
+/// Space around the drawing, in pixels.
+const PADDING: Real = 10.0;
+/// Number of segments approximating the round parts of capsules and round cuboids.
+const SUBDIVISIONS: u32 = 32;
+/// Radius of the dots marking points, in pixels.
+const POINT_RADIUS: Real = 3.0;
+/// Length and half width of arrow heads, in pixels.
+const ARROW_HEAD: (Real, Real) = (10.0, 4.0);
+/// Length of the arrows of contact normals, in pixels.
+const NORMAL_LENGTH: Real = 30.0;
+/// Height of the text of labels, in pixels.
+const FONT_SIZE: Real = 14.0;
+
+/// How an element is drawn.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Style {
+    /// The color of the outline, e.g. `"black"`.
+    pub stroke: &'static str,
+    /// The color of the inside, or `"none"`.
+    pub fill: &'static str,
+    /// The width of the outline, in pixels.
+    pub width: Real,
+    /// Whether the outline is dashed.
+    pub dashed: bool,
+}
+
+impl Style {
+    /// An outline of `color`, not filled.
+    pub const fn stroke(color: &'static str) -> Self {
+        Style {
+            stroke: color,
+            fill: "none",
+            width: 1.5,
+            dashed: false,
+        }
+    }
+
+    /// An outline of `stroke` filled with `fill`.
+    pub const fn filled(stroke: &'static str, fill: &'static str) -> Self {
+        Style {
+            fill,
+            ..Style::stroke(stroke)
+        }
+    }
+
+    /// The same style with a dashed outline.
+    pub const fn dashed(self) -> Self {
+        Style {
+            dashed: true,
+            ..self
+        }
+    }
+
+    /// The SVG attributes of the style.
+    fn attributes(&self) -> String {
+        let dash = if self.dashed {
+            r#" stroke-dasharray="6 4""#
+        } else {
+            ""
+        };
+        format!(
+            r#"stroke="{}" fill="{}" stroke-width="{}"{dash}"#,
+            self.stroke, self.fill, self.width
+        )
+    }
+}
+
+/// A drawing, in the coordinates of the scene: `x` to the right and `y` up.
+///
+/// The drawing is cropped to what was drawn, so shapes can be placed anywhere.
+#[derive(Clone, Debug)]
+pub struct Svg {
+    scale: Real,
+    elements: Vec<String>,
+    /// The pixel bounds of what was drawn.
+    bounds: Aabb,
+}
+
+impl Default for Svg {
+    fn default() -> Self {
+        Svg::new(DEFAULT_SCALE)
+    }
+}
+
+impl Svg {
+    /// An empty drawing where a unit of length is `scale` pixels long.
+    pub fn new(scale: Real) -> Self {
+        Svg {
+            scale,
+            elements: Vec::new(),
+            bounds: Aabb::new_invalid(),
+        }
+    }
+
+    /// Draws `shape` at the position `pos`.
+    ///
+    /// # Panics
+    ///
+    /// If the shape is not a ball, cuboid, round cuboid, capsule, convex polygon, triangle,
+    /// segment, polyline, triangle mesh or compound of those.
+    pub fn shape(&mut self, shape: &dyn Shape, pos: &Isometry<Real>, style: Style) -> &mut Self {
+        let transformed =
+            |points: &[Point<Real>]| points.iter().map(|p| pos * p).collect::<Vec<_>>();
+        if let Some(ball) = shape.as_ball() {
+            self.circle(&pos.translation.vector.into(), ball.radius, style)
+        } else if let Some(cuboid) = shape.as_cuboid() {
+            self.polygon(&transformed(&cuboid.to_polyline()), style)
+        } else if let Some(round_cuboid) = shape.as_round_cuboid() {
+            self.polygon(&transformed(&round_cuboid.to_polyline(SUBDIVISIONS)), style)
+        } else if let Some(capsule) = shape.as_capsule() {
+            self.polygon(&transformed(&capsule.to_polyline(SUBDIVISIONS)), style)
+        } else if let Some(polygon) = shape.as_convex_polygon() {
+            self.polygon(&transformed(polygon.points()), style)
+        } else if let Some(triangle) = shape.as_triangle() {
+            self.polygon(&transformed(triangle.vertices()), style)
+        } else if let Some(segment) = shape.as_segment() {
+            self.segment(&(pos * segment.a), &(pos * segment.b), style)
+        } else if let Some(polyline) = shape.as_polyline() {
+            for segment in polyline.segments() {
+                self.segment(&(pos * segment.a), &(pos * segment.b), style);
+            }
+            self
+        } else if let Some(trimesh) = shape.as_trimesh() {
+            for triangle in trimesh.triangles() {
+                self.polygon(&transformed(triangle.vertices()), style);
+            }
+            self
+        } else if let Some(compound) = shape.as_compound() {
+            for (part_pos, part) in compound.shapes() {
+                self.shape(&**part, &(pos * part_pos), style);
+            }
+            self
+        } else {
+            panic!("Cannot draw shapes of type {:?}", shape.shape_type())
+        }
+    }
+
+    /// Draws the outline of `aabb`.
+    pub fn aabb(&mut self, aabb: &Aabb, style: Style) -> &mut Self {
+        self.polygon(&aabb.vertices(), style)
+    }
+
+    /// Draws the outline of `sphere`.
+    pub fn bounding_sphere(&mut self, sphere: &BoundingSphere, style: Style) -> &mut Self {
+        self.circle(&sphere.center, sphere.radius, style)
+    }
+
+    /// Draws `ray` as an arrow, from its origin to its point at `time_of_impact`.
+    pub fn ray(&mut self, ray: &Ray, time_of_impact: Real, style: Style) -> &mut Self {
+        self.arrow(&ray.origin, &ray.point_at(time_of_impact), style)
+    }
+
+    /// Draws the points of `contact`, given in world space like [`parry2d::query::contact`]
+    /// returns them, and its normal at the first point.
+    pub fn contact(&mut self, contact: &Contact, style: Style) -> &mut Self {
+        let normal_end = contact.point1 + *contact.normal1 * (NORMAL_LENGTH / self.scale);
+        self.segment(&contact.point1, &contact.point2, style.dashed())
+            .point(&contact.point1, style)
+            .point(&contact.point2, style)
+            .arrow(&contact.point1, &normal_end, style)
+    }
+
+    /// Draws a dot at `point`.
+    pub fn point(&mut self, point: &Point<Real>, style: Style) -> &mut Self {
+        let center = self.pixel(point);
+        self.include(&center, POINT_RADIUS);
+        self.elements.push(format!(
+            r#"<circle cx="{:.2}" cy="{:.2}" r="{POINT_RADIUS}" fill="{}"/>"#,
+            center.x, center.y, style.stroke
+        ));
+        self
+    }
+
+    /// Draws the segment from `a` to `b`.
+    pub fn segment(&mut self, a: &Point<Real>, b: &Point<Real>, style: Style) -> &mut Self {
+        let (a, b) = (self.pixel(a), self.pixel(b));
+        self.include(&a, style.width);
+        self.include(&b, style.width);
+        self.elements.push(format!(
+            r#"<line x1="{:.2}" y1="{:.2}" x2="{:.2}" y2="{:.2}" {}/>"#,
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+            style.attributes()
+        ));
+        self
+    }
+
+    /// Draws an arrow from `from` to `to`, with its head at `to`.
+    pub fn arrow(&mut self, from: &Point<Real>, to: &Point<Real>, style: Style) -> &mut Self {
+        self.segment(from, to, style);
+        let (from, to) = (self.pixel(from), self.pixel(to));
+        let Some(dir) = (to - from).try_normalize(Real::EPSILON) else {
+            return self;
+        };
+        let (length, half_width) = ARROW_HEAD;
+        let side = Vector::new(-dir.y, dir.x) * half_width;
+        let base = to - dir * length;
+        let head = [base + side, to, base - side];
+        for point in &head {
+            self.include(point, 0.0);
+        }
+        self.elements.push(format!(
+            r#"<polygon points="{}" fill="{}"/>"#,
+            points_attribute(&head),
+            style.stroke
+        ));
+        self
+    }
+
+    /// Writes `text` centered on `point`.
+    pub fn label(&mut self, point: &Point<Real>, text: &str, style: Style) -> &mut Self {
+        let center = self.pixel(point);
+        // An estimate of the width of the text, enough to keep it inside the drawing.
+        let half_width = text.chars().count() as Real * FONT_SIZE * 0.3;
+        self.include(&(center + Vector::new(-half_width, -FONT_SIZE / 2.0)), 0.0);
+        self.include(&(center + Vector::new(half_width, FONT_SIZE / 2.0)), 0.0);
+        let text = text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        self.elements.push(format!(
+            r#"<text x="{:.2}" y="{:.2}" fill="{}" font-family="sans-serif" font-size="{FONT_SIZE}" text-anchor="middle" dominant-baseline="middle">{text}</text>"#,
+            center.x, center.y, style.stroke
+        ));
+        self
+    }
+
+    /// The SVG document of the drawing.
+    pub fn render(&self) -> String {
+        let (mins, maxs) = if self.elements.is_empty() {
+            (Point::origin(), Point::origin())
+        } else {
+            (self.bounds.mins, self.bounds.maxs)
+        };
+        let (width, height) = (
+            maxs.x - mins.x + 2.0 * PADDING,
+            maxs.y - mins.y + 2.0 * PADDING,
+        );
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{:.2} {:.2} {width:.2} {height:.2}" width="{width:.2}" height="{height:.2}">"#,
+            mins.x - PADDING,
+            mins.y - PADDING,
+        );
+        svg.push('\n');
+        for element in &self.elements {
+            svg.push_str("  ");
+            svg.push_str(element);
+            svg.push('\n');
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Writes the SVG document of the drawing to `path`.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.render())
+    }
+
+    /// Draws the circle of `radius` around `center`.
+    fn circle(&mut self, center: &Point<Real>, radius: Real, style: Style) -> &mut Self {
+        let center = self.pixel(center);
+        let radius = radius * self.scale;
+        self.include(&center, radius + style.width);
+        self.elements.push(format!(
+            r#"<circle cx="{:.2}" cy="{:.2}" r="{radius:.2}" {}/>"#,
+            center.x,
+            center.y,
+            style.attributes()
+        ));
+        self
+    }
+
+    /// Draws the closed polygon of `vertices`.
+    fn polygon(&mut self, vertices: &[Point<Real>], style: Style) -> &mut Self {
+        let vertices = vertices
+            .iter()
+            .map(|vertex| self.pixel(vertex))
+            .collect::<Vec<_>>();
+        for vertex in &vertices {
+            self.include(vertex, style.width);
+        }
+        self.elements.push(format!(
+            r#"<polygon points="{}" {}/>"#,
+            points_attribute(&vertices),
+            style.attributes()
+        ));
+        self
+    }
+
+    /// The position in pixels of `point`, `y` pointing down.
+    fn pixel(&self, point: &Point<Real>) -> Point<Real> {
+        // Adding zero turns `-0.0` into `0.0`, which would otherwise be printed as `-0.00`.
+        Point::new(point.x * self.scale + 0.0, -point.y * self.scale + 0.0)
+    }
+
+    /// Grows the bounds of the drawing to contain the disk of `radius` pixels around `pixel`.
+    fn include(&mut self, pixel: &Point<Real>, radius: Real) {
+        let extent = Vector::repeat(radius);
+        self.bounds.take_point(pixel - extent);
+        self.bounds.take_point(pixel + extent);
+    }
+}
+
+/// The `points` attribute of an SVG polygon with the pixel positions `points`.
+fn points_attribute(points: &[Point<Real>]) -> String {
+    points
+        .iter()
+        .map(|point| format!("{:.2},{:.2}", point.x, point.y))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,137 @@
+//! Draws every figure, so that the assertions of their scenarios run with the examples, and
+//! checks what each figure shows.
+
+use illustrations::figures::{self, figures};
+
+/// The value of the attribute `name` of the SVG `element`.
+fn attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!(" {name}=\""))? + name.len() + 3;
+    let length = element[start..].find('"')?;
+    Some(&element[start..start + length])
+}
+
+/// The elements of the SVG document `svg`, one per line, without the `<svg>` element.
+fn elements(svg: &str) -> Vec<&str> {
+    svg.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("<svg") && !line.starts_with("</svg"))
+        .collect()
+}
+
+/// The texts of the labels of `svg`.
+fn labels(svg: &str) -> Vec<&str> {
+    elements(svg)
+        .into_iter()
+        .filter_map(|element| element.strip_prefix("<text "))
+        .filter_map(|element| element.split_once('>'))
+        .filter_map(|(_, text)| text.strip_suffix("</text>"))
+        .collect()
+}
+
+/// The number of elements of `svg` that start with `tag` and contain every one of `parts`.
+fn count(svg: &str, tag: &str, parts: &[&str]) -> usize {
+    elements(svg)
+        .into_iter()
+        .filter(|element| element.starts_with(tag) && parts.iter().all(|p| element.contains(p)))
+        .count()
+}
+
+#[test]
+fn every_figure_is_cropped_to_its_drawing() {
+    for (name, svg) in figures() {
+        let svg = svg.render();
+        let root = svg.lines().next().unwrap();
+        let view_box = attribute(root, "viewBox")
+            .unwrap()
+            .split(' ')
+            .map(|value| value.parse::<f32>().unwrap())
+            .collect::<Vec<_>>();
+        let [x, y, width, height] = view_box[..] else {
+            panic!("{name} has the view box {view_box:?}");
+        };
+        assert!(width > 0.0 && height > 0.0, "{name} is empty");
+        assert_eq!(attribute(root, "width"), Some(&*format!("{width:.2}")));
+
+        for element in elements(&svg) {
+            let coordinates = match &element[..element.find(' ').unwrap()] {
+                "<polygon" => attribute(element, "points").unwrap().replace(',', " "),
+                "<line" => ["x1", "y1", "x2", "y2"]
+                    .map(|name| attribute(element, name).unwrap())
+                    .join(" "),
+                _ => ["cx", "cy", "x", "y"]
+                    .into_iter()
+                    .filter_map(|name| attribute(element, name))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            };
+            let radius = attribute(element, "r").map_or(0.0, |r| r.parse().unwrap());
+            let coordinates = coordinates
+                .split(' ')
+                .map(|value| value.parse::<f32>().unwrap())
+                .collect::<Vec<_>>();
+            for point in coordinates.chunks(2) {
+                assert!(
+                    x <= point[0] - radius
+                        && point[0] + radius <= x + width
+                        && y <= point[1] - radius
+                        && point[1] + radius <= y + height,
+                    "{name}: {element} is outside of the view box"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn bounding_volumes_surround_the_polygon() {
+    let svg = figures::bounding_volumes().render();
+    assert_eq!(
+        labels(&svg),
+        ["Bounding sphere", "AABB", "OBB", "Convex hull"]
+    );
+    // The concave polygon, in each panel.
+    assert_eq!(count(&svg, "<line", &["stroke=\"black\""]), 20);
+    // The sphere, the AABB, the OBB and the hull.
+    assert_eq!(count(&svg, "<circle", &["stroke=\"#c0392b\""]), 1);
+    assert_eq!(count(&svg, "<polygon", &["stroke=\"#c0392b\""]), 3);
+}
+
+#[test]
+fn proximity_shows_the_closest_points_within_the_margin() {
+    let svg = figures::proximity().render();
+    assert_eq!(labels(&svg), ["Intersecting", "Within margin", "Disjoint"]);
+    assert_eq!(count(&svg, "<circle", &["fill=\"#f2e0c9\""]), 3);
+    assert_eq!(count(&svg, "<polygon", &["stroke-dasharray"]), 3);
+    // Only the ball within the margin has closest points.
+    assert_eq!(count(&svg, "<line", &[]), 1);
+    assert_eq!(count(&svg, "<circle", &["r=\"3\""]), 2);
+}
+
+#[test]
+fn solid_ray_cast_shows_the_normal_of_the_non_solid_hit() {
+    let svg = figures::solid_ray_cast().render();
+    assert_eq!(
+        labels(&svg),
+        [
+            "solid: true, time_of_impact: 0",
+            "solid: false, time_of_impact: 2"
+        ]
+    );
+    // Both rays, and the normal where the non-solid ray hits the border.
+    assert_eq!(count(&svg, "<line", &["stroke-dasharray"]), 2);
+    assert_eq!(count(&svg, "<line", &["stroke=\"#c0392b\""]), 1);
+}
+
+#[test]
+fn time_of_impact_shows_the_shapes_when_they_start_touching() {
+    let svg = figures::time_of_impact().render();
+    assert_eq!(
+        labels(&svg),
+        ["time_of_impact: 0", "time_of_impact: 0.20", "no impact"]
+    );
+    // The velocity of both shapes, in each panel.
+    assert_eq!(count(&svg, "<line", &[]), 6);
+    // Only the shapes that touch later are drawn where they touch.
+    assert_eq!(count(&svg, "<polygon", &["stroke-dasharray"]), 1);
+    assert_eq!(count(&svg, "<circle", &["stroke-dasharray"]), 1);
+}
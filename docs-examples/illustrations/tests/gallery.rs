@@ -0,0 +1,73 @@
+//! Renders every shape of the gallery, so that shapes parry cannot discretize are caught, and
+//! checks that each image shows its whole shape.
+
+use illustrations::gallery::gallery;
+use illustrations::obj::to_obj;
+use illustrations::raster::{decode_png, encode_png, render_png, same_image};
+
+/// The size of the images rendered by the tests, in pixels.
+const SIZE: u32 = 64;
+/// The color of the background of the images.
+const BACKGROUND: [u8; 3] = [255, 255, 255];
+
+#[test]
+fn every_shape_is_exported_to_obj() {
+    for (name, mesh) in gallery() {
+        assert!(!mesh.1.is_empty(), "{name} has no triangles");
+        let obj = to_obj(&mesh);
+        let count = |prefix: &str| obj.lines().filter(|line| line.starts_with(prefix)).count();
+        assert_eq!(count("v "), mesh.0.len(), "{name}");
+        assert_eq!(count("f "), mesh.1.len(), "{name}");
+        assert_eq!(obj.lines().count(), mesh.0.len() + mesh.1.len(), "{name}");
+    }
+}
+
+#[test]
+fn every_shape_is_framed_in_its_image() {
+    let size = SIZE as usize;
+    for (name, mesh) in gallery() {
+        let (width, height, pixels) = decode_png(&render_png(&mesh, SIZE)).unwrap();
+        assert_eq!((width, height), (SIZE, SIZE), "{name}");
+
+        let covered = pixels.iter().filter(|&&pixel| pixel != BACKGROUND).count();
+        let coverage = covered as f32 / pixels.len() as f32;
+        assert!(
+            (0.1..0.8).contains(&coverage),
+            "{name} covers {coverage} of its image"
+        );
+        // Nothing is cut off by the borders of the image.
+        let border =
+            (0..size).flat_map(|i| [i, i * size, i * size + size - 1, pixels.len() - 1 - i]);
+        for i in border {
+            assert_eq!(pixels[i], BACKGROUND, "{name} touches the border at {i}");
+        }
+    }
+}
+
+#[test]
+fn images_differing_by_a_few_pixels_look_the_same() {
+    let (_, mesh) = gallery().remove(0);
+    let png = render_png(&mesh, SIZE);
+    let (_, _, pixels) = decode_png(&png).unwrap();
+    let pixels = pixels.concat();
+    let with_changes = |count: usize, delta: u8| {
+        let mut changed = pixels.clone();
+        for pixel in changed.chunks_mut(3).take(count) {
+            pixel[0] = if pixel[0] < 128 {
+                pixel[0] + delta
+            } else {
+                pixel[0] - delta
+            };
+        }
+        encode_png(&changed, SIZE)
+    };
+    let pixel_count = (SIZE * SIZE) as usize;
+
+    // Every pixel slightly off.
+    assert!(same_image(&png, &with_changes(pixel_count, 16)));
+    // 1% of the pixels completely off.
+    assert!(same_image(&png, &with_changes(pixel_count / 100, 128)));
+    assert!(!same_image(&png, &with_changes(pixel_count / 50, 128)));
+    assert!(!same_image(&png, &render_png(&mesh, SIZE / 2)));
+    assert!(!same_image(&png, b"not a png"));
+}
@@ -0,0 +1,135 @@
+//! Detection of the identifiers documented by templates that parry does not define anymore.
+
+use crate::diagnostics::{line_column, Diagnostic, DiagnosticKind, Report};
+use crate::directory::{collect_templates, read_template};
+use crate::fences::find_fences;
+use crate::rustdoc::crate_api;
+use crate::Config;
+use regex::Regex;
+use std::{collections::HashSet, path::Path};
+
+/// Names of the standard library, or of the language, used by templates.
+pub(crate) const STD_NAMES: &[&str] = &[
+    "Arc", "Box", "Copy", "Err", "None", "Ok", "Option", "Rc", "Result", "Self", "Send", "Some",
+    "String", "Sync", "Vec",
+];
+
+/// Reports the paths written in the inline code of the templates under `templates_dir` that
+/// do not resolve to public items of the [`Config::crates`] and [`Config::extra_crates`], at
+/// the versions the examples of `examples_dir` depend on.
+///
+/// Only inline code made of a single path, like `Aabb::new_invalid()`, `.as_composite_shape()`
+/// or `query::contact(...)`, is checked, ignoring the arguments of the call. Segments starting
+/// with an uppercase letter, like types and enum variants, are checked, as well as the ones
+/// after `.` or `::`, like methods and fields. A segment following a type must be one of its
+/// associated items, fields or variants. The [`Config::undocumented_items`] are accepted
+/// anywhere. The snippets index, starting with the [`Config::index_header`], is skipped.
+pub fn check_api(templates_dir: &Path, examples_dir: &Path, config: &Config) -> Report {
+    let documented = [&config.crates[..], &config.extra_crates[..]].concat();
+    let api = match crate_api(examples_dir, &documented) {
+        Ok(api) => api,
+        Err(error) => return missing_api(examples_dir, &documented, error).into(),
+    };
+    let crates = api.crates.join(", ");
+
+    let re_code = Regex::new(r"`([^`\n]+)`").unwrap();
+    let re_file = Regex::new(r"^[\w.-]+\.(toml|rs|mdx?|lock|json)$").unwrap();
+    let re_path =
+        Regex::new(r"^((?:\.|::)?[A-Za-z_]\w*(?:(?:\.|::)[A-Za-z_]\w*)*)(?:<.*>)?(?:\(.*\))?$")
+            .unwrap();
+    let re_segment = Regex::new(r"(\.|::)?([A-Za-z_]\w*)").unwrap();
+    let mut report = Report::default();
+    let templates = match collect_templates(templates_dir) {
+        Ok(templates) => templates,
+        Err(diagnostic) => return diagnostic.into(),
+    };
+
+    for template in templates {
+        let content = match read_template(&template) {
+            Ok(content) => content,
+            Err(diagnostic) => {
+                report.diagnostics.push(diagnostic);
+                continue;
+            }
+        };
+        // The items of the snippets index are read from the same API, and its other inline
+        // code names regions.
+        if content.starts_with(&config.index_header) {
+            continue;
+        }
+        let code_lines = find_fences(&content)
+            .iter()
+            .flat_map(|fence| fence.open..fence.open + fence.body.lines().count() + 2)
+            .collect::<HashSet<_>>();
+        for code in re_code.captures_iter(&content) {
+            let code = code.get(1).unwrap();
+            let (line, _) = line_column(&content, code.start());
+            if code_lines.contains(&(line - 1)) || re_file.is_match(code.as_str()) {
+                continue;
+            }
+            let Some(path) = re_path.captures(code.as_str()) else {
+                continue;
+            };
+            let mut previous: Option<&str> = None;
+            for segment in re_segment.captures_iter(&path[1]) {
+                let name = segment.get(2).unwrap();
+                let parent = previous.replace(name.as_str());
+                let is_member = segment.get(1).is_some();
+                let is_type = name.as_str().starts_with(char::is_uppercase) && name.len() > 1;
+                if !(is_member || is_type)
+                    || STD_NAMES.contains(&name.as_str())
+                    || config
+                        .undocumented_items
+                        .iter()
+                        .any(|item| item == name.as_str())
+                {
+                    continue;
+                }
+                let message = match parent {
+                    Some(parent) if api.is_type(parent) => {
+                        if api.is_member(parent, name.as_str()) {
+                            continue;
+                        }
+                        format!(
+                            "`{}` is not a member of `{parent}` in {crates}",
+                            name.as_str()
+                        )
+                    }
+                    _ => {
+                        if api.items.contains(name.as_str()) {
+                            continue;
+                        }
+                        format!("`{}` is not a public item of {crates}", name.as_str())
+                    }
+                };
+                let (line, column) = line_column(&content, code.start() + name.start());
+                report.diagnostics.push(Diagnostic {
+                    file: template.display().to_string(),
+                    line: Some(line),
+                    column: Some(column),
+                    kind: DiagnosticKind::UnknownItem,
+                    path: None,
+                    marker: None,
+                    message,
+                });
+            }
+        }
+    }
+
+    report.success = Some(format!("✅ Every path resolves in {crates}."));
+    report.failure = Some(format!(
+        "❌ {} path segment(s) not found in {crates}.",
+        report.diagnostics.len()
+    ));
+    report
+}
+
+/// The diagnostic that the API of the `crates` used by the examples of `examples_dir` could
+/// not be read, because of `error`.
+pub(crate) fn missing_api(examples_dir: &Path, crates: &[String], error: String) -> Diagnostic {
+    Diagnostic::in_file(
+        examples_dir,
+        DiagnosticKind::MissingApi,
+        format!("could not read the API of {}: {error}", crates.join(", ")),
+    )
+}
@@ -0,0 +1,275 @@
+//! Cache of the generated outputs, to skip the templates whose inputs did not change.
+
+use crate::diagnostics::{Diagnostic, DiagnosticKind};
+use crate::inject::extract_region;
+use crate::outputs::module_files;
+use crate::tags::load_tags;
+use crate::variables::{has_placeholders, Variables};
+use crate::{resolve_path, template_root, Config};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Name of the cache file, written in the output directory.
+const CACHE_FILE: &str = ".inject_file_cache.json";
+
+/// The content hashes an output was generated from.
+#[derive(Serialize, Deserialize)]
+pub struct Entry {
+    /// Hash of the template, of the values of its variables and of the version of
+    /// `inject_file`.
+    template: String,
+    /// Hash of the regions loaded by the template, or of the whole examples whose output
+    /// is loaded, with their modules and the `Cargo.lock` of the root.
+    snippets: String,
+    /// Hash of the generated output.
+    output: String,
+}
+
+/// The cache of the outputs of a directory, by output path.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Cache {
+    outputs: BTreeMap<PathBuf, Entry>,
+}
+
+impl Cache {
+    /// Loads the cache of `output_dir`, empty if it does not exist or cannot be read.
+    pub fn load(output_dir: &Path) -> Self {
+        fs::read_to_string(output_dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Writes this cache to `output_dir`.
+    pub fn save(&self, output_dir: &Path) -> Result<(), Diagnostic> {
+        let path = output_dir.join(CACHE_FILE);
+        fs::write(&path, serde_json::to_string_pretty(self).unwrap()).map_err(|error| {
+            Diagnostic::in_file(
+                &path,
+                DiagnosticKind::Io,
+                format!("could not write the cache: {error}"),
+            )
+        })
+    }
+
+    /// Whether `output_path` was generated from the same template and snippets as
+    /// `input_path` has now, and was not modified since.
+    pub fn is_fresh(&self, input_path: &Path, output_path: &Path, config: &Config) -> bool {
+        let Some(entry) = self.outputs.get(output_path) else {
+            return false;
+        };
+        let Ok(output) = fs::read_to_string(output_path) else {
+            return false;
+        };
+        let Some((template, snippets)) = input_hashes(input_path, config) else {
+            return false;
+        };
+        entry.template == template && entry.snippets == snippets && entry.output == hash(&output)
+    }
+
+    /// Records that `output` was generated to `output_path` from the template at
+    /// `input_path`.
+    pub fn insert(&mut self, input_path: &Path, output_path: &Path, config: &Config, output: &str) {
+        let Some((template, snippets)) = input_hashes(input_path, config) else {
+            return;
+        };
+        let entry = Entry {
+            template,
+            snippets,
+            output: hash(output),
+        };
+        self.outputs.insert(output_path.to_path_buf(), entry);
+    }
+
+    /// Forgets `output_path`, e.g. when its template fails to inject.
+    pub fn remove(&mut self, output_path: &Path) {
+        self.outputs.remove(output_path);
+    }
+}
+
+/// The hashes of the template at `input_path` and of the snippets it loads.
+///
+/// The template is hashed with its variables filled, the values of every variable and the
+/// version of `inject_file`, so that it changes with the versions of the crates and with the
+/// injection itself. Returns `None` if the template has no root.
+fn input_hashes(input_path: &Path, config: &Config) -> Option<(String, String)> {
+    let mut template = fs::read_to_string(input_path).unwrap_or_default();
+    let root = template_root(input_path, config).ok()?;
+    // Without variables, the template fails to inject and is not cached.
+    let variables = Variables::load(&root).ok()?;
+    if has_placeholders(&template) {
+        template = variables.substitute(&template).0;
+    }
+    let mut template_hasher = Fnv::new();
+    template_hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+    for (name, value) in variables.values() {
+        template_hasher.write(name.as_bytes());
+        template_hasher.write(value.as_bytes());
+    }
+    template_hasher.write(config.crates.join(",").as_bytes());
+    template_hasher.write(template.as_bytes());
+
+    let mut hasher = Fnv::new();
+    let mut runs_examples = false;
+    for tag in load_tags(&template, config) {
+        let path = resolve_path(&root, &tag.path);
+        let attributes = format!(
+            "{:?}",
+            (
+                &tag.path,
+                &tag.marker,
+                tag.dedent,
+                &tag.lines,
+                tag.output,
+                tag.f64,
+            )
+        );
+        hasher.write(attributes.as_bytes());
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let source = source.replace("\r\n", "\n");
+        if tag.output {
+            // The output of an example may depend on any of its lines, of its modules, and on
+            // the versions of its dependencies.
+            hasher.write(source.as_bytes());
+            for module in module_files(&path) {
+                hasher.write(module.display().to_string().as_bytes());
+                hasher.write(&fs::read(&module).unwrap_or_default());
+            }
+            runs_examples = true;
+        } else {
+            match extract_region(&source, &path, &tag) {
+                Ok(region) => hasher.write(region.as_bytes()),
+                Err(error) => hasher.write(format!("{error:?}").as_bytes()),
+            }
+        }
+    }
+    if runs_examples {
+        hasher.write(&fs::read(root.join("Cargo.lock")).unwrap_or_default());
+    }
+    Some((template_hasher.finish(), hasher.finish()))
+}
+
+/// The hash of `content`, in hexadecimal.
+fn hash(content: &str) -> String {
+    let mut hasher = Fnv::new();
+    hasher.write(content.as_bytes());
+    hasher.finish()
+}
+
+/// The 64-bit FNV-1a hash, the same across runs, platforms and Rust versions, unlike the
+/// `DefaultHasher` of the standard library.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    /// Hashes `bytes`, prefixed with their length so that consecutive writes cannot be
+    /// confused with a single one.
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in (bytes.len() as u64).to_le_bytes().iter().chain(bytes) {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    /// The hash of everything written, in hexadecimal.
+    fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    /// A template loading a region of `example.rs` and the output of `run.rs`, which declares
+    /// the module `helper`, with an up-to-date output recorded in a cache.
+    fn cached(name: &str) -> (TempDir, Config, Cache) {
+        let dir = TempDir::new(name);
+        dir.write("Cargo.lock", "");
+        dir.write(
+            "example.rs",
+            "// DOCUSAURUS: a start\nlet a = 1;\n// DOCUSAURUS: a stop\n",
+        );
+        dir.write("run.rs", "mod helper;\nfn main() {}\n");
+        dir.write("helper.rs", "pub fn help() {}\n");
+        dir.write(
+            "page.mdx",
+            "<load path='/example.rs' marker='a' />\n<load path='/run.rs' marker='b' output='true' />\n",
+        );
+        dir.write("page.out", "output");
+        let config = Config {
+            root: Some(dir.path().to_path_buf()),
+            ..Config::default()
+        };
+        let mut cache = Cache::default();
+        cache.insert(
+            &dir.path().join("page.mdx"),
+            &dir.path().join("page.out"),
+            &config,
+            "output",
+        );
+        (dir, config, cache)
+    }
+
+    fn is_fresh(dir: &TempDir, config: &Config, cache: &Cache) -> bool {
+        cache.is_fresh(
+            &dir.path().join("page.mdx"),
+            &dir.path().join("page.out"),
+            config,
+        )
+    }
+
+    #[test]
+    fn unchanged_inputs_hit() {
+        let (dir, config, cache) = cached("cache_hit");
+        assert!(is_fresh(&dir, &config, &cache));
+        // Lines outside of the loaded region do not change it.
+        dir.write(
+            "example.rs",
+            "// DOCUSAURUS: a start\nlet a = 1;\n// DOCUSAURUS: a stop\nlet b = 2;\n",
+        );
+        assert!(is_fresh(&dir, &config, &cache));
+    }
+
+    #[test]
+    fn unknown_and_modified_outputs_miss() {
+        let (dir, config, mut cache) = cached("cache_miss");
+        dir.write("page.out", "edited");
+        assert!(!is_fresh(&dir, &config, &cache));
+        dir.write("page.out", "output");
+        cache.remove(&dir.path().join("page.out"));
+        assert!(!is_fresh(&dir, &config, &cache));
+    }
+
+    #[test]
+    fn changed_inputs_invalidate() {
+        let changes = [
+            ("page.mdx", "<load path='/example.rs' marker='a' />\n"),
+            (
+                "example.rs",
+                "// DOCUSAURUS: a start\nlet a = 2;\n// DOCUSAURUS: a stop\n",
+            ),
+            ("run.rs", "mod helper;\nfn main() { helper::help() }\n"),
+            ("helper.rs", "pub fn help() { println!() }\n"),
+            (
+                "Cargo.lock",
+                "[[package]]\nname = \"parry2d\"\nversion = \"0.17.4\"\n",
+            ),
+        ];
+        for (i, (path, content)) in changes.into_iter().enumerate() {
+            let (dir, config, cache) = cached(&format!("cache_invalidation_{i}"));
+            dir.write(path, content);
+            assert!(!is_fresh(&dir, &config, &cache), "{path}");
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! Compilation of the hand-written Rust code fences of templates.
+
+use crate::diagnostics::{Diagnostic, DiagnosticKind, Report};
+use crate::directory::{collect_templates, read_template};
+use crate::fences::{find_fences, Fence};
+use crate::scratch::{
+    absolute_dependency_paths, cargo, copy_lockfile, scratch_package, write_file,
+};
+use crate::tags::find_tags;
+use crate::{missing_root, template_root, Config};
+use regex::Regex;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A hand-written fence, compiled as a cargo example.
+struct Snippet {
+    /// The template of the fence.
+    template: PathBuf,
+    /// Line of the template where the body of the fence starts, starting at 1.
+    first_line: usize,
+    /// Number of lines of the example before the body of the fence.
+    prelude_lines: usize,
+}
+
+/// Checks that every ` ```rust ` fence of the templates under `templates_dir` compiles.
+///
+/// Fences loading an example are skipped, since the example itself is compiled, as well as
+/// fences with `ignore` in their info string. The others are compiled as the `main` function
+/// of an example of a scratch copy of the [`Config::fence_manifest`] package of the 2D or 3D
+/// examples, depending on the `*3` types they use, after the [`Config::fence_prelude`].
+///
+/// Finding no fence to compile is an error, so that a check run on the wrong directory, or
+/// on templates that only load examples, does not pass silently.
+pub fn check_fences(templates_dir: &Path, config: &Config) -> Report {
+    let re_3d = Regex::new(r"\b[A-Z]\w*3\b").unwrap();
+    // Examples of each package, by manifest, then by example name.
+    let mut packages: BTreeMap<PathBuf, BTreeMap<String, (Snippet, String)>> = BTreeMap::new();
+    let mut report = Report::default();
+    let templates = match collect_templates(templates_dir) {
+        Ok(templates) => templates,
+        Err(diagnostic) => return diagnostic.into(),
+    };
+
+    for template in templates {
+        let content = match read_template(&template) {
+            Ok(content) => content,
+            Err(diagnostic) => {
+                report.diagnostics.push(diagnostic);
+                continue;
+            }
+        };
+        let root = match template_root(&template, config) {
+            Ok(root) => root,
+            Err(message) => {
+                report.diagnostics.push(missing_root(&template, message));
+                continue;
+            }
+        };
+        for fence in find_fences(&content) {
+            if !is_hand_written_rust(&fence) {
+                continue;
+            }
+            let dim = if re_3d.is_match(&fence.body) { 3 } else { 2 };
+            let manifest = root.join(config.fence_manifest.replace("{dim}", &dim.to_string()));
+            let name = format!(
+                "{}_{}",
+                template.file_stem().unwrap().to_string_lossy(),
+                fence.open + 1
+            )
+            .replace(|c: char| !c.is_alphanumeric(), "_");
+            let prelude = config.fence_prelude.replace("{dim}", &dim.to_string());
+            let snippet = Snippet {
+                template: template.clone(),
+                first_line: fence.open + 2,
+                prelude_lines: prelude.lines().count(),
+            };
+            let source = format!("{prelude}{}}}\n", fence.body);
+            packages
+                .entry(manifest)
+                .or_default()
+                .insert(name, (snippet, source));
+        }
+    }
+
+    if packages.is_empty() && report.diagnostics.is_empty() {
+        return Diagnostic::in_file(
+            templates_dir,
+            DiagnosticKind::NothingToCheck,
+            "no hand-written rust code fence to compile".to_string(),
+        )
+        .into();
+    }
+
+    let mut count = 0;
+    for (manifest, snippets) in &packages {
+        count += snippets.len();
+        let diagnostics = match compile_snippets(manifest, snippets) {
+            Ok(diagnostics) => diagnostics,
+            Err(message) => vec![Diagnostic {
+                file: manifest.display().to_string(),
+                line: None,
+                column: None,
+                kind: DiagnosticKind::FenceCompilation,
+                path: None,
+                marker: None,
+                message,
+            }],
+        };
+        report.diagnostics.extend(diagnostics);
+    }
+
+    report.success = Some(format!("✅ {count} code fence(s) compile."));
+    report.failure = Some(format!(
+        "❌ {} compilation error(s) in the code fences of the templates.",
+        report.diagnostics.len()
+    ));
+    report
+}
+
+/// Whether `fence` is a Rust fence written in the template, not loading an example.
+fn is_hand_written_rust(fence: &Fence) -> bool {
+    fence.language() == "rust"
+        && !fence.info.split_whitespace().any(|word| word == "ignore")
+        && find_tags(&fence.body).is_empty()
+}
+
+/// The name of the scratch package the fences compiled with `manifest` are written to.
+fn package_name(manifest: &Path) -> String {
+    format!("{}_fences", manifest.parent().unwrap().display()).replace(['/', '\\', '.'], "_")
+}
+
+/// Compiles `snippets` as the examples of a scratch copy of the package of `manifest`, and
+/// returns a diagnostic per compilation error, located in the templates.
+fn compile_snippets(
+    manifest: &Path,
+    snippets: &BTreeMap<String, (Snippet, String)>,
+) -> Result<Vec<Diagnostic>, String> {
+    let manifest_content = fs::read_to_string(manifest)
+        .map_err(|error| format!("could not read {}: {error}", manifest.display()))?;
+    let manifest_content = absolute_dependency_paths(&manifest_content, manifest.parent().unwrap());
+    let dir = scratch_package(&package_name(manifest), &manifest_content)?;
+    copy_lockfile(manifest.parent().unwrap(), &dir)?;
+    for (name, (_, source)) in snippets {
+        write_file(&dir.join(format!("examples/{name}.rs")), source)?;
+    }
+
+    let output = cargo(
+        &dir,
+        &[
+            "check",
+            "--quiet",
+            "--keep-going",
+            "--message-format",
+            "short",
+            "--examples",
+        ],
+    )?;
+    if output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    let re_error = Regex::new(r"^examples/(\w+)\.rs:(\d+):(\d+): (error.*)$").unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let diagnostics = stderr
+        .lines()
+        .filter_map(|line| {
+            let (_, [name, line, column, message]) = re_error.captures(line)?.extract();
+            let (snippet, _) = snippets.get(name)?;
+            let line = line.parse::<usize>().ok()?;
+            Some(Diagnostic {
+                file: snippet.template.display().to_string(),
+                line: Some(snippet.first_line + line.saturating_sub(snippet.prelude_lines + 1)),
+                column: column.parse().ok(),
+                kind: DiagnosticKind::FenceCompilation,
+                path: None,
+                marker: None,
+                message: format!("this rust fence does not compile: {message}"),
+            })
+        })
+        .collect::<Vec<_>>();
+    if diagnostics.is_empty() {
+        return Err(format!(
+            "the code fences could not be compiled:\n{}",
+            stderr.trim_end()
+        ));
+    }
+    Ok(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    /// Checks the fences of the `template`, compiled with a package without dependencies.
+    fn check(name: &str, template: &str) -> (Report, PathBuf) {
+        let dir = TempDir::new(name);
+        let manifest = dir.write(
+            "2d/Cargo.toml",
+            "[package]\nname = \"fences\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        );
+        dir.write("2d/src/main.rs", "fn main() {}\n");
+        let template = dir.write("templates/page.mdx", template);
+        let config = Config {
+            root: Some(dir.path().to_path_buf()),
+            fence_manifest: "{dim}d/Cargo.toml".to_string(),
+            fence_prelude: "#![allow(unused)]\nfn main() {\n".to_string(),
+            ..Config::default()
+        };
+
+        let report = check_fences(&dir.path().join("templates"), &config);
+        let _ = fs::remove_dir_all(
+            std::env::temp_dir()
+                .join("inject_file")
+                .join(package_name(&manifest)),
+        );
+        (report, template)
+    }
+
+    #[test]
+    fn errors_are_located_in_the_templates() {
+        let template = "\
+# Page
+
+```rust
+let one: u32 = 1;
+```
+
+```rust
+let two: u32 = \"two\";
+```
+
+```rust ignore
+not rust
+```
+
+```rust
+<load path='/example.rs' marker='a' />
+```
+";
+        let (report, template) = check("fences_errors", template);
+        assert_eq!(report.diagnostics.len(), 1, "{:?}", report.diagnostics);
+        let diagnostic = &report.diagnostics[0];
+        assert_eq!(diagnostic.kind, DiagnosticKind::FenceCompilation);
+        assert_eq!(diagnostic.file, template.display().to_string());
+        assert_eq!(diagnostic.line, Some(8));
+        assert_eq!(report.success.unwrap(), "✅ 2 code fence(s) compile.");
+    }
+
+    #[test]
+    fn templates_without_fences_to_compile_fail() {
+        let template = "```rust\n<load path='/example.rs' marker='a' />\n```\n";
+        let (report, _) = check("fences_none", template);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].kind, DiagnosticKind::NothingToCheck);
+    }
+}
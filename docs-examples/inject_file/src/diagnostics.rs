@@ -0,0 +1,286 @@
+//! Diagnostics of the injection and of the checks, and the reports printing them as text or JSON.
+
+use crate::inject::{
+    ErrorType, IncorrectLines, IncorrectMarker, IncorrectOutput, IncorrectRegion, InjectError,
+    LinesError,
+};
+use serde::Serialize;
+use std::path::Path;
+
+/// How diagnostics are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable messages.
+    #[default]
+    Human,
+    /// One JSON object per line.
+    Json,
+}
+
+impl Format {
+    /// Parses the value of the `--format` option.
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "human" => Ok(Format::Human),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "Unknown format {value}, expected `human` or `json`."
+            )),
+        }
+    }
+}
+
+/// The kind of problem a [`Diagnostic`] reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticKind {
+    /// A `<load>` or `<load-dims>` tag is malformed.
+    IncorrectTag,
+    /// A `<load>` tag points to a file that could not be read.
+    IncorrectPath,
+    /// A `<load>` tag points to a marker missing from its file.
+    IncorrectMarker,
+    /// The `lines` of a `<load>` tag start at 0, are reversed, or go past the end of its
+    /// region.
+    IncorrectLines,
+    /// The markers of an example are unbalanced or overlap.
+    IncorrectRegion,
+    /// The example of a `<load output='true'>` tag could not be run.
+    IncorrectOutput,
+    /// An example fails to build or panics.
+    FailingExample,
+    /// An example is not built by the `-f64` package of its dimension.
+    MissingF64Example,
+    /// A `{{name}}` placeholder names an unknown variable.
+    UnknownVariable,
+    /// A generated output differs from its template.
+    OutOfDate,
+    /// A marker is not loaded by any template.
+    UnusedMarker,
+    /// A marker is defined several times in the same file.
+    DuplicatedMarker,
+    /// The `f64` variant of an example does not compile.
+    F64Compilation,
+    /// A hand-written Rust code fence does not compile.
+    FenceCompilation,
+    /// A check found nothing to verify, e.g. no hand-written Rust code fence.
+    NothingToCheck,
+    /// A link points to a missing page, anchor or image.
+    BrokenLink,
+    /// An identifier of the inline code of a template is not defined by parry.
+    UnknownItem,
+    /// A file or directory could not be read or written.
+    Io,
+    /// The root of the paths of a template could not be found.
+    MissingRoot,
+    /// The API of the documented crates could not be read.
+    MissingApi,
+    /// The `Cargo.lock` the versions of the variables are read from is missing or outdated.
+    IncorrectLock,
+}
+
+/// A problem found in a file, optionally located at a line and column.
+#[derive(Clone, Debug, Serialize)]
+pub struct Diagnostic {
+    /// The file where the problem was found.
+    pub file: String,
+    /// The line of the problem, starting at 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// The column of the problem, in characters, starting at 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    /// What the problem is.
+    pub kind: DiagnosticKind,
+    /// The path involved in the problem, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// The marker involved in the problem, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// A diagnostic for an error of the template at `template`.
+    pub fn from_inject_error(template: &Path, error: &InjectError) -> Self {
+        let (kind, path, marker, message) = match &error.error {
+            ErrorType::IncorrectTag => (
+                DiagnosticKind::IncorrectTag,
+                None,
+                None,
+                "a <load> or <load-dims> tag was incorrectly written, maybe missing path, name or marker, with an unknown attribute, or not using `'` for string delimiters".to_string(),
+            ),
+            ErrorType::IncorrectPath(path) => (
+                DiagnosticKind::IncorrectPath,
+                Some(path.clone()),
+                None,
+                format!("path {} not found", path),
+            ),
+            ErrorType::IncorrectMarker(IncorrectMarker { marker, filepath }) => (
+                DiagnosticKind::IncorrectMarker,
+                Some(filepath.clone()),
+                Some(marker.clone()),
+                format!("marker {} not found in {}", marker, filepath),
+            ),
+            ErrorType::IncorrectLines(IncorrectLines {
+                marker,
+                lines,
+                reason,
+            }) => (
+                DiagnosticKind::IncorrectLines,
+                None,
+                Some(marker.clone()),
+                match reason {
+                    LinesError::ZeroBased => format!(
+                        "lines {} of marker {} start at 0, but line numbers are 1-based",
+                        lines, marker
+                    ),
+                    LinesError::Reversed => format!(
+                        "lines {} of marker {} are reversed: start > end",
+                        lines, marker
+                    ),
+                    LinesError::PastEnd(line_count) => format!(
+                        "lines {} are out of the {} lines of marker {}",
+                        lines, line_count, marker
+                    ),
+                },
+            ),
+            ErrorType::IncorrectRegion(IncorrectRegion { filepath, error }) => (
+                DiagnosticKind::IncorrectRegion,
+                Some(filepath.clone()),
+                Some(error.marker().to_string()),
+                format!("in {}, {}", filepath, error),
+            ),
+            ErrorType::IncorrectOutput(IncorrectOutput { filepath, error }) => (
+                DiagnosticKind::IncorrectOutput,
+                Some(filepath.clone()),
+                None,
+                format!("could not run {} to inject its output: {}", filepath, error),
+            ),
+            ErrorType::UnknownVariable(name) => (
+                DiagnosticKind::UnknownVariable,
+                None,
+                None,
+                format!("unknown variable {{{{{}}}}}", name),
+            ),
+            ErrorType::IncorrectTemplate(reason) => (
+                DiagnosticKind::Io,
+                None,
+                None,
+                format!("could not read the template: {}", reason),
+            ),
+            ErrorType::MissingRoot(reason) => (DiagnosticKind::MissingRoot, None, None, reason.clone()),
+            ErrorType::IncorrectLock(reason) => (
+                DiagnosticKind::IncorrectLock,
+                None,
+                None,
+                format!("could not fill the variables: {}", reason),
+            ),
+        };
+        Diagnostic {
+            file: template.display().to_string(),
+            line: Some(error.line),
+            column: Some(error.column),
+            kind,
+            path,
+            marker,
+            message,
+        }
+    }
+
+    /// A diagnostic about the whole `file`, not located in it.
+    pub fn in_file(file: &Path, kind: DiagnosticKind, message: String) -> Self {
+        Diagnostic {
+            file: file.display().to_string(),
+            line: None,
+            column: None,
+            kind,
+            path: None,
+            marker: None,
+            message,
+        }
+    }
+
+    /// Prints this diagnostic to stderr.
+    pub fn emit(&self, format: Format) {
+        match format {
+            Format::Human => {
+                let mut location = self.file.clone();
+                if let Some(line) = self.line {
+                    location.push_str(&format!(":{line}"));
+                    if let Some(column) = self.column {
+                        location.push_str(&format!(":{column}"));
+                    }
+                }
+                eprintln!("❌ ERROR: {}: {}", location, self.message);
+            }
+            Format::Json => {
+                eprintln!("{}", serde_json::to_string(self).unwrap());
+            }
+        }
+    }
+}
+
+/// The outcome of an operation: what it did, and the problems it found.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Messages about what was done, e.g. the regenerated outputs or their diffs.
+    pub messages: Vec<String>,
+    /// The problems found, in the order they were found.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Conclusion printed when no problem was found.
+    pub success: Option<String>,
+    /// Conclusion printed after the diagnostics, in the human format, when problems were found.
+    pub failure: Option<String>,
+}
+
+impl From<Diagnostic> for Report {
+    /// The report of an operation stopped by `diagnostic`.
+    fn from(diagnostic: Diagnostic) -> Self {
+        Report {
+            diagnostics: vec![diagnostic],
+            ..Report::default()
+        }
+    }
+}
+
+impl Report {
+    /// Whether no problem was found.
+    pub fn is_success(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Prints the messages of this report to stdout, then its diagnostics to stderr.
+    ///
+    /// Returns `true` if there was no problem.
+    pub fn print(&self, format: Format) -> bool {
+        for message in &self.messages {
+            println!("{message}");
+        }
+        for diagnostic in &self.diagnostics {
+            diagnostic.emit(format);
+        }
+        if self.is_success() {
+            if let Some(success) = &self.success {
+                println!("{success}");
+            }
+            return true;
+        }
+        if let (Format::Human, Some(failure)) = (format, &self.failure) {
+            eprintln!("{failure}");
+        }
+        false
+    }
+}
+
+/// The line and column, in characters, of the byte `offset` of `text`, both starting at 1.
+pub fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
@@ -0,0 +1,343 @@
+//! Injection of every template of a directory into an output directory.
+
+use crate::cache::Cache;
+use crate::diagnostics::{Diagnostic, DiagnosticKind, Report};
+use crate::inject::InjectError;
+use crate::{diff_output, inject_file, out_of_date, Config};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// What injecting a set of templates did.
+#[derive(Default)]
+pub struct Outcome {
+    /// Outputs that were written.
+    pub written: Vec<PathBuf>,
+    /// Outputs that were skipped, their template and snippets being unchanged.
+    pub unchanged: Vec<PathBuf>,
+    /// Templates that could not be injected, with their errors.
+    pub failures: Vec<(PathBuf, Vec<InjectError>)>,
+    /// Templates whose existing output differs from the injected result, with that output.
+    pub out_of_date: Vec<(PathBuf, PathBuf)>,
+    /// Outputs that could not be written.
+    pub errors: Vec<Diagnostic>,
+    /// Unified diffs of the outputs out of date or, with `print_diff`, regenerated.
+    pub diffs: Vec<String>,
+}
+
+impl Outcome {
+    /// The report listing the diffs, the written and unchanged outputs, and the errors.
+    pub fn report(&self) -> Report {
+        let mut report = Report {
+            messages: self.diffs.clone(),
+            ..Report::default()
+        };
+        for output_path in &self.written {
+            report
+                .messages
+                .push(format!("🔄 Regenerated {}", output_path.display()));
+        }
+        if !self.unchanged.is_empty() {
+            report
+                .messages
+                .push(format!("✅ {} output(s) unchanged.", self.unchanged.len()));
+        }
+        for (input_path, errors) in &self.failures {
+            report.diagnostics.extend(
+                errors
+                    .iter()
+                    .map(|error| Diagnostic::from_inject_error(input_path, error)),
+            );
+        }
+        for (input_path, output_path) in &self.out_of_date {
+            report
+                .diagnostics
+                .push(out_of_date(input_path, output_path));
+        }
+        report.diagnostics.extend(self.errors.iter().cloned());
+        report.failure = Some(format!(
+            "❌ {} template(s) failed to inject, {} output(s) out of date.",
+            self.failures.len(),
+            self.out_of_date.len()
+        ));
+        report
+    }
+}
+
+/// Injects every `.mdx` template under `input_dir` and writes them to the mirrored
+/// location under `output_dir`.
+///
+/// If `config.check` is set, outputs are compared with the existing files instead of being
+/// written. Otherwise, unless `config.no_cache` is set, the outputs whose template and
+/// snippets did not change since they were generated are skipped.
+///
+/// Errors of every template are reported together once all of them have been processed.
+pub fn inject_directory(input_dir: &Path, output_dir: &Path, config: &Config) -> Report {
+    let templates = match collect_templates(input_dir) {
+        Ok(templates) => templates,
+        Err(diagnostic) => return diagnostic.into(),
+    };
+    let mut cache = (!config.check && !config.no_cache).then(|| Cache::load(output_dir));
+    let mut outcome = inject_templates(input_dir, output_dir, &templates, config, cache.as_mut());
+    if let Some(cache) = &cache {
+        if let Err(diagnostic) = cache.save(output_dir) {
+            outcome.errors.push(diagnostic);
+        }
+    }
+    outcome.report()
+}
+
+/// Injects the given `templates`, located under `input_dir`, to the mirrored location under
+/// `output_dir`.
+///
+/// If `config.check` is set, outputs are compared with the existing files instead of being
+/// written. Otherwise, the outputs up to date in `cache` are skipped, and the diff of the
+/// others is kept if `config.print_diff` is set.
+pub fn inject_templates(
+    input_dir: &Path,
+    output_dir: &Path,
+    templates: &[PathBuf],
+    config: &Config,
+    mut cache: Option<&mut Cache>,
+) -> Outcome {
+    let mut outcome = Outcome::default();
+    for input_path in templates {
+        let output_path = output_path(input_dir, output_dir, input_path);
+        if let Some(cache) = &cache {
+            if cache.is_fresh(input_path, &output_path, config) {
+                outcome.unchanged.push(output_path);
+                continue;
+            }
+        }
+        let result = match inject_file(input_path, config) {
+            Ok(result) => result,
+            Err(partial) => {
+                if let Some(cache) = &mut cache {
+                    cache.remove(&output_path);
+                }
+                outcome.failures.push((input_path.clone(), partial.errors));
+                continue;
+            }
+        };
+        let diff = diff_output(input_path, &output_path, &result);
+
+        if config.check {
+            if let Some(diff) = diff {
+                outcome.diffs.push(diff);
+                outcome.out_of_date.push((input_path.clone(), output_path));
+            }
+        } else {
+            if config.print_diff {
+                outcome.diffs.extend(diff);
+            }
+            let written = output_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(&output_path, &result));
+            if let Err(error) = written {
+                outcome.errors.push(Diagnostic::in_file(
+                    &output_path,
+                    DiagnosticKind::Io,
+                    format!("could not write the output: {error}"),
+                ));
+                continue;
+            }
+            if let Some(cache) = &mut cache {
+                cache.insert(input_path, &output_path, config, &result);
+            }
+            outcome.written.push(output_path);
+        }
+    }
+    outcome
+}
+
+/// The path under `output_dir` mirroring the path of `input_path` under `input_dir`.
+pub fn output_path(input_dir: &Path, output_dir: &Path, input_path: &Path) -> PathBuf {
+    output_dir.join(input_path.strip_prefix(input_dir).unwrap())
+}
+
+/// Recursively collects the `.mdx` files under `dir`, sorted by path.
+///
+/// The error is the diagnostic of the first directory that could not be read.
+pub fn collect_templates(dir: &Path) -> Result<Vec<PathBuf>, Diagnostic> {
+    let mut templates = Vec::new();
+    collect_templates_rec(dir, &mut templates)?;
+    Ok(templates)
+}
+
+fn collect_templates_rec(dir: &Path, templates: &mut Vec<PathBuf>) -> Result<(), Diagnostic> {
+    let mut entries = read_dir(dir)?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_templates_rec(&path, templates)?;
+        } else if path.extension().is_some_and(|ext| ext == "mdx") {
+            templates.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// The paths of the entries of `dir`, in no particular order.
+pub fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, Diagnostic> {
+    let entries = fs::read_dir(dir)
+        .and_then(|entries| entries.map(|entry| Ok(entry?.path())).collect())
+        .map_err(|error| {
+            Diagnostic::in_file(
+                dir,
+                DiagnosticKind::Io,
+                format!("could not read the directory: {error}"),
+            )
+        })?;
+    Ok(entries)
+}
+
+/// Reads the template at `path`, with `\n` line endings.
+pub fn read_template(path: &Path) -> Result<String, Diagnostic> {
+    fs::read_to_string(path)
+        .map(|content| content.replace("\r\n", "\n"))
+        .map_err(|error| {
+            Diagnostic::in_file(
+                path,
+                DiagnosticKind::Io,
+                format!("could not read the template: {error}"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+
+    const EXAMPLE: &str = "\
+// DOCUSAURUS: a start
+let a = 1;
+// DOCUSAURUS: a stop
+";
+
+    /// The config resolving the `<load>` paths of the templates against `dir`.
+    fn config(dir: &TempDir) -> Config {
+        Config {
+            root: Some(dir.path().to_path_buf()),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn nested_templates_are_collected_and_other_files_skipped() {
+        let dir = TempDir::new("directory_collect");
+        dir.write("templates/b.mdx", "");
+        dir.write("templates/a/deeper/c.mdx", "");
+        dir.write("templates/a.mdx", "");
+        dir.write("templates/notes.md", "");
+        dir.write("templates/a/image.svg", "");
+        dir.write("templates/a/mdx", "");
+
+        let templates = collect_templates(&dir.path().join("templates")).unwrap();
+        let templates = templates
+            .iter()
+            .map(|path| path.strip_prefix(dir.path()).unwrap().to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            templates,
+            [
+                "templates/a/deeper/c.mdx",
+                "templates/a.mdx",
+                "templates/b.mdx"
+            ]
+        );
+    }
+
+    #[test]
+    fn outputs_mirror_the_templates() {
+        let dir = TempDir::new("directory_mirror");
+        dir.write("examples/example.rs", EXAMPLE);
+        dir.write(
+            "templates/page.mdx",
+            "<load path='/examples/example.rs' marker='a' />\n",
+        );
+        dir.write("templates/nested/page.mdx", "# Title\n");
+        dir.write("templates/nested/notes.txt", "not a template");
+        let output_dir = dir.path().join("output");
+
+        let report = inject_directory(&dir.path().join("templates"), &output_dir, &config(&dir));
+        assert!(report.diagnostics.is_empty());
+        assert_eq!(
+            fs::read_to_string(output_dir.join("page.mdx")).unwrap(),
+            "let a = 1;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(output_dir.join("nested/page.mdx")).unwrap(),
+            "# Title\n"
+        );
+        assert!(!output_dir.join("nested/notes.txt").exists());
+    }
+
+    #[test]
+    fn errors_of_every_template_are_reported_together() {
+        let dir = TempDir::new("directory_errors");
+        dir.write("examples/example.rs", EXAMPLE);
+        dir.write(
+            "templates/a.mdx",
+            "<load path='/examples/example.rs' marker='x' />\n",
+        );
+        dir.write(
+            "templates/b.mdx",
+            "<load path='/examples/example.rs' marker='a' />\n",
+        );
+        dir.write(
+            "templates/c/d.mdx",
+            "<load path='/examples/missing.rs' marker='a' />\n",
+        );
+        let output_dir = dir.path().join("output");
+
+        let report = inject_directory(&dir.path().join("templates"), &output_dir, &config(&dir));
+        let failed = report
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.file.as_str(), diagnostic.kind))
+            .collect::<Vec<_>>();
+        let templates = dir.path().join("templates");
+        assert_eq!(
+            failed,
+            [
+                (
+                    templates.join("a.mdx").to_str().unwrap(),
+                    DiagnosticKind::IncorrectMarker
+                ),
+                (
+                    templates.join("c/d.mdx").to_str().unwrap(),
+                    DiagnosticKind::IncorrectPath
+                ),
+            ]
+        );
+        // The template without errors is still written.
+        assert!(output_dir.join("b.mdx").exists());
+        assert!(!output_dir.join("a.mdx").exists());
+    }
+
+    #[test]
+    fn check_reports_outputs_out_of_date_without_writing() {
+        let dir = TempDir::new("directory_check");
+        dir.write("templates/a.mdx", "new\n");
+        dir.write("templates/b.mdx", "same\n");
+        dir.write("output/a.mdx", "old\n");
+        dir.write("output/b.mdx", "same\n");
+        let config = Config {
+            check: true,
+            ..config(&dir)
+        };
+
+        let output_dir = dir.path().join("output");
+        let report = inject_directory(&dir.path().join("templates"), &output_dir, &config);
+        assert_eq!(report.diagnostics.len(), 1);
+        assert_eq!(report.diagnostics[0].kind, DiagnosticKind::OutOfDate);
+        assert!(report.diagnostics[0].file.ends_with("a.mdx"));
+        assert_eq!(
+            fs::read_to_string(output_dir.join("a.mdx")).unwrap(),
+            "old\n"
+        );
+    }
+}
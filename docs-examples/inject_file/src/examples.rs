@@ -0,0 +1,243 @@
+//! Execution of every example, reporting the region of the assertions that fail.
+
+use crate::diagnostics::{Diagnostic, DiagnosticKind, Report};
+use crate::outputs::run_cargo_example;
+use crate::regions::MarkerSyntax;
+use crate::scratch::cargo;
+use regex::Regex;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// An example target of a package of the workspace.
+struct Example {
+    /// The `Cargo.toml` of its package.
+    manifest: PathBuf,
+    /// The name of the target, e.g. `bounding_volumes2_f64`.
+    name: String,
+    /// Its source file.
+    path: PathBuf,
+}
+
+/// Runs every cargo example of the `*/rust` packages of the workspace at `root`, e.g.
+/// `2d/rust/examples/*.rs`, and the `f64` builds of the same sources declared by
+/// `2d-f64/rust/Cargo.toml`.
+///
+/// The examples are built with the `dev` profile, like `cargo test` builds them, and with
+/// `--locked`, so that they run against the committed `Cargo.lock`. Set `CARGO_NET_OFFLINE`
+/// to run them without network access.
+///
+/// An example that panics is reported at the line of the panic, with the innermost
+/// `DOCUSAURUS` region containing it. One that fails to build is reported with the end of the
+/// output of cargo. Examples missing from the `[[example]]` list of the `-f64` package of
+/// their dimension are reported too.
+pub fn check_examples(root: &Path) -> Report {
+    let mut report = Report::default();
+    let examples = match collect_examples(root) {
+        Ok(examples) => examples,
+        Err(message) => {
+            report.diagnostics.push(Diagnostic {
+                file: root.join("Cargo.toml").display().to_string(),
+                line: None,
+                column: None,
+                kind: DiagnosticKind::FailingExample,
+                path: None,
+                marker: None,
+                message: format!("could not list the examples: {message}"),
+            });
+            report.failure = Some("❌ Could not list the examples.".to_string());
+            return report;
+        }
+    };
+    let missing = missing_f64_examples(&examples);
+    let missing_count = missing.len();
+    report.diagnostics.extend(missing);
+    let mut failed = 0;
+    for example in &examples {
+        let diagnostic = match run_cargo_example(&example.manifest, &example.name, false) {
+            Ok(output) if output.status.success() => continue,
+            Ok(output) => failure_diagnostic(
+                root,
+                example,
+                &output.status.to_string(),
+                &String::from_utf8_lossy(&output.stderr),
+            ),
+            Err(message) => Diagnostic {
+                file: example.path.display().to_string(),
+                line: None,
+                column: None,
+                kind: DiagnosticKind::FailingExample,
+                path: None,
+                marker: None,
+                message: format!("example {}: {message}", example.name),
+            },
+        };
+        report.diagnostics.push(diagnostic);
+        failed += 1;
+    }
+
+    report.success = Some(format!(
+        "✅ {} example(s) run successfully.",
+        examples.len()
+    ));
+    report.failure = Some(format!(
+        "❌ {failed} of {} example(s) failed, {missing_count} example(s) are not built against the f64 crates.",
+        examples.len()
+    ));
+    report
+}
+
+/// The example targets of the `*/rust` packages of the workspace at `root`, sorted by source
+/// path and name, as listed by `cargo metadata`.
+fn collect_examples(root: &Path) -> Result<Vec<Example>, String> {
+    let output = cargo(root, &["metadata", "--no-deps", "--format-version", "1"])?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    let metadata: serde_json::Value =
+        serde_json::from_slice(&output.stdout).map_err(|error| error.to_string())?;
+    let packages = metadata["packages"].as_array().cloned().unwrap_or_default();
+
+    let mut examples = Vec::new();
+    for package in &packages {
+        let Some(manifest) = package["manifest_path"].as_str().map(PathBuf::from) else {
+            continue;
+        };
+        if !manifest.parent().is_some_and(|dir| dir.ends_with("rust")) {
+            continue;
+        }
+        let targets = package["targets"].as_array().cloned().unwrap_or_default();
+        for target in &targets {
+            let is_example = target["kind"]
+                .as_array()
+                .is_some_and(|kinds| kinds.iter().any(|kind| kind == "example"));
+            let (Some(name), Some(path)) = (target["name"].as_str(), target["src_path"].as_str())
+            else {
+                continue;
+            };
+            if is_example {
+                let path = Path::new(path);
+                examples.push(Example {
+                    manifest: manifest.clone(),
+                    name: name.to_string(),
+                    path: fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()),
+                });
+            }
+        }
+    }
+    examples.sort_by(|a, b| (&a.path, &a.name).cmp(&(&b.path, &b.name)));
+    Ok(examples)
+}
+
+/// The diagnostics of the examples of the `*/rust` packages that the matching `*-f64/rust`
+/// package, e.g. `2d-f64/rust` for `2d/rust`, does not build from the same source.
+fn missing_f64_examples(examples: &[Example]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for example in examples {
+        let Some(dim_dir) = example.manifest.parent().and_then(Path::parent) else {
+            continue;
+        };
+        let dim = dim_dir.file_name().unwrap_or_default().to_string_lossy();
+        let f64_manifest = dim_dir
+            .with_file_name(format!("{dim}-f64"))
+            .join("rust/Cargo.toml");
+        if dim.ends_with("-f64") || !f64_manifest.is_file() {
+            continue;
+        }
+        let is_built = examples
+            .iter()
+            .any(|other| other.manifest == f64_manifest && other.path == example.path);
+        if is_built {
+            continue;
+        }
+        let file_name = example
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        diagnostics.push(Diagnostic {
+            file: example.path.display().to_string(),
+            line: None,
+            column: None,
+            kind: DiagnosticKind::MissingF64Example,
+            path: Some(f64_manifest.display().to_string()),
+            marker: None,
+            message: format!(
+                "example {} is not built against the f64 crates, add it to {}:\n\n[[example]]\nname = \"{}_f64\"\npath = \"../../{dim}/rust/examples/{file_name}\"",
+                example.name,
+                f64_manifest.display(),
+                example.name
+            ),
+        });
+    }
+    diagnostics
+}
+
+/// The diagnostic of `example` exiting with `status`, given its `stderr`.
+///
+/// If it panicked in its own source, the diagnostic is located at the panic and names the
+/// region containing it. Relative panic locations are resolved against the workspace `root`.
+fn failure_diagnostic(root: &Path, example: &Example, status: &str, stderr: &str) -> Diagnostic {
+    let re_panic = Regex::new(r"panicked at (.+):(\d+):(\d+):$").unwrap();
+    let lines = stderr.lines().collect::<Vec<_>>();
+    let name = &example.name;
+    let mut diagnostic = Diagnostic {
+        file: example.path.display().to_string(),
+        line: None,
+        column: None,
+        kind: DiagnosticKind::FailingExample,
+        path: None,
+        marker: None,
+        message: format!(
+            "example {name} failed with {status}:\n{}",
+            lines[lines.len().saturating_sub(5)..].join("\n")
+        ),
+    };
+
+    let Some((index, panic)) = lines
+        .iter()
+        .enumerate()
+        .find_map(|(i, line)| Some((i, re_panic.captures(line)?)))
+    else {
+        return diagnostic;
+    };
+    // The panic message spans the lines up to the note about backtraces.
+    let message = lines[index + 1..]
+        .iter()
+        .take_while(|line| !line.starts_with("note:"))
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let location = Path::new(&panic[1]);
+    let line = panic[2].parse::<usize>().unwrap();
+    if fs::canonicalize(root.join(location)).ok().as_ref() != Some(&example.path) {
+        diagnostic.message = format!(
+            "example {name} panicked in {}: {message}",
+            location.display()
+        );
+        return diagnostic;
+    }
+
+    diagnostic.line = Some(line);
+    diagnostic.column = panic[3].parse().ok();
+    let source = fs::read_to_string(&example.path).unwrap_or_default();
+    let region = MarkerSyntax::new("//")
+        .parse_regions(&source)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|region| region.start < line - 1 && line - 1 < region.stop)
+        .max_by_key(|region| region.start);
+    diagnostic.message = match region {
+        Some(region) => {
+            let message = format!(
+                "example {name} panicked in region {}: {message}",
+                region.name
+            );
+            diagnostic.marker = Some(region.name);
+            message
+        }
+        None => format!("example {name} panicked outside of any region: {message}"),
+    };
+    diagnostic
+}
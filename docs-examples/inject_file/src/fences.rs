@@ -0,0 +1,91 @@
+//! Code fences of templates, and the languages inferred for the fences of injected snippets.
+
+use crate::languages::Language;
+use crate::tags::load_tags;
+use crate::Config;
+use std::path::Path;
+
+/// A closed code fence of a template.
+pub struct Fence {
+    /// The info string of the fence, e.g., `rust` or `rust title="..."`, empty for a bare fence.
+    pub info: String,
+    /// Index of the line opening the fence, starting at 0.
+    pub open: usize,
+    /// The lines between the opening and closing lines, with their newlines.
+    pub body: String,
+}
+
+impl Fence {
+    /// The language of the fence, i.e. the first word of its info string.
+    pub fn language(&self) -> &str {
+        self.info.split_whitespace().next().unwrap_or_default()
+    }
+}
+
+/// Finds every closed code fence of `template`, opened and closed by lines starting with
+/// ` ``` `. A fence left open at the end of the template is ignored.
+pub fn find_fences(template: &str) -> Vec<Fence> {
+    let lines = template.split_inclusive('\n').collect::<Vec<_>>();
+    let is_fence = |line: &str| line.trim_start().starts_with("```");
+    let mut fences = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        if !is_fence(lines[i]) {
+            i += 1;
+            continue;
+        }
+        let open = i;
+        let Some(close) = lines[open + 1..]
+            .iter()
+            .position(|l| is_fence(l))
+            .map(|close| open + 1 + close)
+        else {
+            break;
+        };
+        fences.push(Fence {
+            info: lines[open]
+                .trim()
+                .trim_start_matches('`')
+                .trim()
+                .to_string(),
+            open,
+            body: lines[open + 1..close].concat(),
+        });
+        i = close + 1;
+    }
+    fences
+}
+
+/// Gives a language to the code fences of `template` opened without one.
+///
+/// A bare ` ``` ` fence takes the language of the first file loaded between it and its closing
+/// fence, from the extension of that file, or `text` if the output of that file is loaded.
+/// Fences with a language, or loading no file of a known language, are kept as is. Only fence
+/// lines change, so the lines of `<load>` tags are unchanged.
+pub fn infer_fence_languages(template: &str, config: &Config) -> String {
+    let mut lines = template
+        .split_inclusive('\n')
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    for fence in find_fences(template) {
+        if !fence.info.is_empty() {
+            continue;
+        }
+        let language = load_tags(&fence.body, config).first().and_then(|tag| {
+            if tag.output {
+                Some("text")
+            } else {
+                Language::from_path(Path::new(&tag.path)).map(|language| language.fence)
+            }
+        });
+        if let Some(language) = language {
+            let line = &lines[fence.open];
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let newline = &line[line.trim_end().len()..];
+            lines[fence.open] = format!("{indent}```{language}{newline}");
+        }
+    }
+    lines.concat()
+}
@@ -0,0 +1,322 @@
+//! Generation of the index page listing every region of the examples.
+
+use crate::api::{missing_api, STD_NAMES};
+use crate::diagnostics::{line_column, Diagnostic, DiagnosticKind, Report};
+use crate::directory::{collect_templates, read_dir, read_template};
+use crate::links::headings;
+use crate::regions::MarkerSyntax;
+use crate::rustdoc::{crate_api, Api};
+use crate::tags::{find_tags, Tag, DIMS};
+use crate::{diff_output, missing_root, out_of_date, resolve_path, template_root, Config};
+use regex::Regex;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A guide section loading a region.
+struct Section {
+    /// The template, relative to the templates directory.
+    page: PathBuf,
+    /// The text of the heading of the section.
+    title: String,
+    /// The anchor of the heading of the section.
+    anchor: String,
+}
+
+/// Generates the index page of every region of the 2D and 3D Rust examples under the root of
+/// `config`, and writes it to `output_path`.
+///
+/// Each region is listed with the items of the [`Config::crates`] it uses and links to the
+/// sections of the
+/// templates under `templates_dir` loading it. The links are relative to the templates
+/// directory, so the page belongs at the root of the output directory. If `config.check` is
+/// set, the page is compared with `output_path` instead of being written.
+pub fn write_snippets_index(templates_dir: &Path, output_path: &Path, config: &Config) -> Report {
+    let mut report = Report::default();
+    let index = match snippets_index(templates_dir, config) {
+        Ok(index) => index,
+        Err(diagnostic) => return diagnostic.into(),
+    };
+    let diff = diff_output(templates_dir, output_path, &index);
+    if config.check {
+        if let Some(diff) = diff {
+            report.messages.push(diff);
+            report
+                .diagnostics
+                .push(out_of_date(templates_dir, output_path));
+        }
+        return report;
+    }
+    if config.print_diff {
+        report.messages.extend(diff);
+    }
+    if let Err(error) = fs::write(output_path, &index) {
+        report.diagnostics.push(Diagnostic::in_file(
+            output_path,
+            DiagnosticKind::Io,
+            format!("could not write the index: {error}"),
+        ));
+        return report;
+    }
+    report
+        .messages
+        .push(format!("🔄 Regenerated {}", output_path.display()));
+    report
+}
+
+/// The content of the index page of the regions of the examples under the root of `config`,
+/// starting with its [`Config::index_header`].
+///
+/// The examples of each dimension are the `.rs` files of the directory of its
+/// [`Config::dims_path`].
+pub fn snippets_index(templates_dir: &Path, config: &Config) -> Result<String, Diagnostic> {
+    let root =
+        template_root(templates_dir, config).map_err(|error| missing_root(templates_dir, error))?;
+    let api = crate_api(&root, &config.crates)
+        .map_err(|error| missing_api(&root, &config.crates, error))?;
+    let sections = loading_sections(templates_dir, &root, config)?;
+    let mut index = config.index_header.clone();
+
+    for dim in DIMS {
+        let dims_example = resolve_path(&root, &config.dims_example(dim, ""));
+        let examples_dir = dims_example.parent().unwrap_or(&root);
+        let mut examples = read_dir(examples_dir)?
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+            .collect::<Vec<_>>();
+        examples.sort();
+
+        index.push_str(&format!("## {dim}D examples\n\n"));
+        index.push_str("| Example | Region | Items | Guide |\n");
+        index.push_str("|---------|--------|-------|-------|\n");
+        for example in examples {
+            let source = fs::read_to_string(&example).map_err(|error| {
+                Diagnostic::in_file(
+                    &example,
+                    DiagnosticKind::Io,
+                    format!("could not read the example: {error}"),
+                )
+            })?;
+            let syntax = MarkerSyntax::new("//");
+            let Ok(regions) = syntax.parse_regions(&source) else {
+                // Incorrect markers are reported by check-markers.
+                continue;
+            };
+            let lines = source.lines().collect::<Vec<_>>();
+            let file_name = example.file_name().unwrap().to_string_lossy();
+            let canonical_path = fs::canonicalize(&example).unwrap_or(example.clone());
+            let mut listed = HashSet::new();
+            for region in &regions {
+                if !listed.insert(&region.name) {
+                    continue;
+                }
+                let code = lines[region.start + 1..region.stop]
+                    .iter()
+                    .filter(|line| !syntax.is_marker_line(line) && !syntax.is_hidden_line(line))
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                let used = used_items(&code, &api, &config.crates)
+                    .iter()
+                    .map(|item| format!("`{item}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let guide = sections
+                    .get(&(canonical_path.clone(), region.name.clone()))
+                    .map(|sections| {
+                        sections
+                            .iter()
+                            .map(|section| {
+                                format!(
+                                    "[{}](./{}#{})",
+                                    section.title,
+                                    section.page.display(),
+                                    section.anchor
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
+                index.push_str(&format!(
+                    "| `{file_name}` | `{}` | {used} | {guide} |\n",
+                    region.name
+                ));
+            }
+        }
+        index.push('\n');
+    }
+    index.truncate(index.trim_end().len() + 1);
+    Ok(index)
+}
+
+/// The sections of the templates under `templates_dir` loading each region, by canonical
+/// example path and marker.
+///
+/// A section is the closest heading above the tag loading the region.
+fn loading_sections(
+    templates_dir: &Path,
+    root: &Path,
+    config: &Config,
+) -> Result<BTreeMap<(PathBuf, String), Vec<Section>>, Diagnostic> {
+    let mut sections: BTreeMap<(PathBuf, String), Vec<Section>> = BTreeMap::new();
+    for template in collect_templates(templates_dir)? {
+        let content = read_template(&template)?;
+        let headings = headings(&content);
+        let page = template.strip_prefix(templates_dir).unwrap().to_path_buf();
+        for tag in find_tags(&content).into_iter().flatten() {
+            let (start, tags) = match tag {
+                Tag::Load(tag) => (tag.range.start, vec![tag]),
+                Tag::Dims(tag) => (
+                    tag.range.start,
+                    tag.load_tags(config)
+                        .into_iter()
+                        .map(|(_, tag)| tag)
+                        .collect(),
+                ),
+            };
+            let (line, _) = line_column(&content, start);
+            let Some(heading) = headings.iter().rev().find(|heading| heading.line < line) else {
+                continue;
+            };
+            for tag in tags.into_iter().filter(|tag| !tag.output) {
+                let Ok(path) = fs::canonicalize(resolve_path(root, &tag.path)) else {
+                    continue;
+                };
+                let region_sections = sections.entry((path, tag.marker)).or_default();
+                if !region_sections
+                    .iter()
+                    .any(|section| section.page == page && section.anchor == heading.anchor)
+                {
+                    region_sections.push(Section {
+                        page: page.clone(),
+                        title: heading.text.clone(),
+                        anchor: heading.anchor.clone(),
+                    });
+                }
+            }
+        }
+    }
+    Ok(sections)
+}
+
+/// The items of the documented `crates` used by `code`, sorted: types, with their associated
+/// function if one is called, like `ConvexPolyhedron::from_convex_hull`, and functions of
+/// modules, like `query::contact`.
+///
+/// Only the structs, enums, unions and traits of `api` are listed, not type aliases or
+/// re-exports of primitive types like `Real`.
+fn used_items(code: &str, api: &Api, crates: &[String]) -> BTreeSet<String> {
+    let re_ignored = Regex::new(r#"//.*|/\*(?s:.*?)\*/|"(?:\\.|[^"\\])*""#).unwrap();
+    let re_path = Regex::new(r"\b[A-Za-z_]\w*(?:::[A-Za-z_]\w*)*").unwrap();
+    let code = re_ignored.replace_all(code, "");
+    let is_crate = |name: &str| crates.iter().any(|krate| krate == name);
+    let mut used = BTreeSet::new();
+
+    for path in re_path.find_iter(&code) {
+        let segments = path.as_str().split("::").collect::<Vec<_>>();
+        // Skip the crate, then the modules, e.g. `parry2d::query::`. The last segment is
+        // an item even if a module has the same name, like `query::contact`.
+        let first = segments[..segments.len() - 1]
+            .iter()
+            .position(|segment| !is_crate(segment) && !api.modules.contains(*segment))
+            .unwrap_or(segments.len() - 1);
+        let name = segments[first];
+        if name.starts_with(char::is_uppercase) {
+            if !api.is_type(name) || STD_NAMES.contains(&name) {
+                continue;
+            }
+            match segments.get(first + 1) {
+                Some(function)
+                    if function.starts_with(char::is_lowercase)
+                        && api.is_member(name, function) =>
+                {
+                    used.insert(format!("{name}::{function}"));
+                }
+                _ => {
+                    used.insert(name.to_string());
+                }
+            }
+        } else if first > 0 && !is_crate(segments[first - 1]) && api.items.contains(name) {
+            used.insert(format!("{}::{name}", segments[first - 1]));
+        }
+    }
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The API of a crate with the modules `query` and `shape`, the function `contact`, the
+    /// types `Ball` and `ConvexPolygon`, and the type alias `Real`.
+    fn api() -> Api {
+        let mut api = Api::default();
+        for item in [
+            "query",
+            "shape",
+            "contact",
+            "Ball",
+            "ConvexPolygon",
+            "new",
+            "Real",
+        ] {
+            api.items.insert(item.to_string());
+        }
+        api.modules.insert("query".to_string());
+        api.modules.insert("shape".to_string());
+        api.members.insert(
+            "Ball".to_string(),
+            HashSet::from(["new".to_string(), "radius".to_string()]),
+        );
+        api.members.insert(
+            "ConvexPolygon".to_string(),
+            HashSet::from(["from_convex_hull".to_string()]),
+        );
+        api
+    }
+
+    fn used_items(code: &str) -> Vec<String> {
+        super::used_items(code, &api(), &["parry2d".to_string()])
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn types_are_listed_with_their_called_function() {
+        let code = "\
+let ball = Ball::new(1.0);
+let radius = ball.radius;
+let hull = parry2d::shape::ConvexPolygon::from_convex_hull(&points).unwrap();
+";
+        assert_eq!(
+            used_items(code),
+            ["Ball::new", "ConvexPolygon::from_convex_hull"]
+        );
+    }
+
+    #[test]
+    fn functions_are_listed_with_their_module() {
+        assert_eq!(
+            used_items("let c = parry2d::query::contact(&pos1, &ball, &pos2, &ball, 0.0);"),
+            ["query::contact"]
+        );
+        // Called without their module, functions are not distinguished from variables.
+        assert!(used_items("let c = contact(&pos1, &ball, &pos2, &ball, 0.0);").is_empty());
+    }
+
+    #[test]
+    fn other_names_are_not_listed() {
+        let code = "\
+use parry2d::math::Real;
+let radius: Real = 1.0;
+let balls: Vec<Ball> = Vec::new();
+let shape: Option<Aabb> = None;
+// Ball::new(2.0)
+println!(\"ConvexPolygon::from_convex_hull\");
+";
+        assert_eq!(used_items(code), ["Ball"]);
+    }
+}
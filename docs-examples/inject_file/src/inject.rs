@@ -0,0 +1,526 @@
+//! Injection of example regions into templates.
+//!
+//! This is adapted from strinject, with every error located at the `<load>` tag causing it.
+//!
+//! strinject cannot be wrapped for this: it substitutes every tag with one regex replacement
+//! and returns its errors without the tag they come from, nor its offset, and it resolves
+//! paths with a plain `fn` pointer, which cannot capture a configured root. The tags are
+//! parsed here instead, keeping the offset of each, and the injection of each tag reports its
+//! own error.
+
+use crate::diagnostics::line_column;
+use crate::fences::infer_fence_languages;
+use crate::languages::Language;
+use crate::outputs::run_example;
+use crate::precision::to_f64;
+use crate::regions::{MarkerSyntax, RegionError};
+use crate::tags::{find_tags, DimsTag, LoadTag, Tag};
+use crate::Config;
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+/// A marker that could not be found in a loaded file.
+#[derive(Debug, PartialEq)]
+pub struct IncorrectMarker {
+    /// Name of the marker.
+    pub marker: String,
+    /// File where the marker should have been found, as written in the tag.
+    pub filepath: String,
+}
+
+/// An error while injecting a `<load>` tag.
+// Variant names are kept from strinject.
+#[allow(clippy::enum_variant_names)]
+#[derive(Debug, PartialEq)]
+pub enum ErrorType {
+    /// A load tag was incorrectly formed in the source text.
+    IncorrectTag,
+    /// Could not read the file at the given path.
+    IncorrectPath(String),
+    /// Could not find the marker in the loaded file.
+    IncorrectMarker(IncorrectMarker),
+    /// The `lines` attribute of a tag is not a valid range of its region.
+    IncorrectLines(IncorrectLines),
+    /// The markers of the loaded file are unbalanced or overlap.
+    IncorrectRegion(IncorrectRegion),
+    /// The loaded example could not be run to inject its output.
+    IncorrectOutput(IncorrectOutput),
+    /// A `{{name}}` placeholder names an unknown variable.
+    UnknownVariable(String),
+    /// The template itself could not be read, with the reason.
+    IncorrectTemplate(String),
+    /// The root of the paths of the template could not be found, with the reason.
+    MissingRoot(String),
+    /// The versions of the variables could not be resolved from the `Cargo.lock` of the
+    /// root, with the reason.
+    IncorrectLock(String),
+}
+
+/// An example whose output could not be captured.
+#[derive(Debug, PartialEq)]
+pub struct IncorrectOutput {
+    /// The example, as written in the tag.
+    pub filepath: String,
+    /// Why it could not be run.
+    pub error: String,
+}
+
+/// A loaded file whose markers do not delimit valid regions.
+#[derive(Debug, PartialEq)]
+pub struct IncorrectRegion {
+    /// The loaded file, as written in the tag.
+    pub filepath: String,
+    /// What is wrong with its markers.
+    pub error: RegionError,
+}
+
+/// A `lines` range that does not fit in the loaded region.
+#[derive(Debug, PartialEq)]
+pub struct IncorrectLines {
+    /// Name of the marker.
+    pub marker: String,
+    /// The requested range, as written in the tag.
+    pub lines: String,
+    /// What is wrong with the range.
+    pub reason: LinesError,
+}
+
+/// What is wrong with a `lines` range.
+#[derive(Debug, PartialEq)]
+pub enum LinesError {
+    /// The range starts at line 0, while line numbers start at 1.
+    ZeroBased,
+    /// The range starts after its end.
+    Reversed,
+    /// The range ends after the last line of the region, which has this number of lines.
+    PastEnd(usize),
+}
+
+/// An error located at the `<load>` tag that caused it.
+#[derive(Debug)]
+pub struct InjectError {
+    /// Line of the tag in the source text, starting at 1.
+    pub line: usize,
+    /// Column of the tag in the source text, in characters, starting at 1.
+    pub column: usize,
+    /// The error itself.
+    pub error: ErrorType,
+}
+
+/// The errors of an injection, with the best result that could be done.
+#[derive(Debug)]
+pub struct PartialInjection {
+    /// The injected text, without the tags that failed.
+    pub result: String,
+    /// The errors, in the order of their tags.
+    pub errors: Vec<InjectError>,
+}
+
+/// Reads the parameter and returns a new string with injected text.
+///
+/// `get_path` maps the path of each `<load>` tag to the file to read. Code fences opened
+/// without a language take the one of the file they load.
+///
+/// Tags with `output='true'` inject the region of what their example prints instead of its
+/// code, the example printing the marker comments around that output. Each example is run
+/// at most once.
+///
+/// `<load-dims>` tags are replaced by a `Tabs` block with a tab per dimension whose example,
+/// at the [`Config::dims_path`] of `config`, has the marker.
+///
+/// On errors, the failed tags are removed from the partial result.
+pub fn inject_with_path(
+    source_text: &str,
+    config: &Config,
+    get_path: impl Fn(&str) -> PathBuf,
+) -> Result<String, PartialInjection> {
+    let source_text = &infer_fence_languages(&source_text.replace("\r\n", "\n"), config);
+    let mut errors = Vec::new();
+    let mut result = String::new();
+    let mut last_end = 0;
+    let mut outputs = HashMap::new();
+
+    for tag in find_tags(source_text) {
+        let tag = match tag {
+            Ok(tag) => tag,
+            Err(start) => {
+                errors.push(located_error(source_text, start, ErrorType::IncorrectTag));
+                continue;
+            }
+        };
+        let range = match &tag {
+            Tag::Load(tag) => tag.range.clone(),
+            Tag::Dims(tag) => tag.range.clone(),
+        };
+        result.push_str(&source_text[last_end..range.start]);
+        last_end = range.end;
+
+        let injected = match &tag {
+            Tag::Load(tag) => load_region(tag, config, &get_path, &mut outputs),
+            Tag::Dims(tag) => load_dims(tag, config, &get_path, &mut outputs),
+        };
+        match injected {
+            Ok(injected) => {
+                result.push_str(&injected);
+                result.push('\n');
+            }
+            Err(error) => errors.push(located_error(source_text, range.start, error)),
+        }
+    }
+    result.push_str(&source_text[last_end..]);
+
+    if !errors.is_empty() {
+        return Err(PartialInjection { result, errors });
+    }
+    Ok(result)
+}
+
+/// The outputs of the examples already run, by path.
+type Outputs = HashMap<PathBuf, Result<String, String>>;
+
+/// Reads the file of `tag`, or the output of its example, and extracts its region.
+fn load_region(
+    tag: &LoadTag,
+    config: &Config,
+    get_path: impl Fn(&str) -> PathBuf,
+    outputs: &mut Outputs,
+) -> Result<String, ErrorType> {
+    let path = get_path(&tag.path);
+    // Reading file from the path of the tag of input file
+    let Ok(mut to_inject) = read_to_string(&path) else {
+        return Err(ErrorType::IncorrectPath(path.display().to_string()));
+    };
+    if tag.output {
+        let output = outputs
+            .entry(path.clone())
+            .or_insert_with(|| run_example(&path));
+        to_inject = output.clone().map_err(|error| {
+            ErrorType::IncorrectOutput(IncorrectOutput {
+                filepath: tag.path.clone(),
+                error,
+            })
+        })?;
+    }
+    let mut to_inject = to_inject.replace("\r\n", "\n");
+    if tag.f64 && !tag.output {
+        to_inject = to_f64(&to_inject, &config.crates);
+    }
+    extract_region(&to_inject, &path, tag)
+}
+
+/// Builds the `Tabs` block of `tag`, skipping the dimensions whose example is missing.
+///
+/// A marker missing from an existing example is an error: dimensions without the marker are
+/// excluded with the `dims` attribute of the tag instead.
+///
+/// With `tag.f64`, each dimension whose `f64` code differs from its `f32` code holds a nested
+/// `Tabs` block with both.
+fn load_dims(
+    tag: &DimsTag,
+    config: &Config,
+    get_path: impl Fn(&str) -> PathBuf,
+    outputs: &mut Outputs,
+) -> Result<String, ErrorType> {
+    let mut tabs = Vec::new();
+    let mut paths = Vec::new();
+    for (dim, load_tag) in tag.load_tags(config) {
+        let f32_tag = LoadTag {
+            f64: false,
+            ..load_tag.clone()
+        };
+        match load_region(&f32_tag, config, &get_path, outputs) {
+            Ok(region) => {
+                let fence = Language::from_path(Path::new(&load_tag.path))
+                    .map_or("", |language| language.fence);
+                let mut content = format!("```{fence}\n{region}\n```");
+                if tag.f64 {
+                    let f64_region = load_region(&load_tag, config, &get_path, outputs)?;
+                    // Identical tabs would suggest that the code differs between precisions.
+                    if f64_region != region {
+                        let f64_content = format!("```{fence}\n{f64_region}\n```");
+                        content = tabs_block(
+                            "precision",
+                            &[
+                                ("f32".to_string(), "f32".to_string(), content),
+                                ("f64".to_string(), "f64".to_string(), f64_content),
+                            ],
+                        );
+                    }
+                }
+                tabs.push((format!("Example {dim}D"), format!("{dim}D"), content));
+            }
+            Err(ErrorType::IncorrectPath(_)) => {}
+            Err(error) => return Err(error),
+        }
+        paths.push(load_tag.path);
+    }
+    if tabs.is_empty() {
+        return Err(ErrorType::IncorrectPath(paths.join(" or ")));
+    }
+    Ok(tabs_block("dim", &tabs))
+}
+
+/// Builds a `Tabs` block of the `group` with the given `(label, value, content)` tabs, the
+/// first one being selected by default.
+fn tabs_block(group: &str, tabs: &[(String, String, String)]) -> String {
+    let mut block = format!(
+        "<Tabs\n  groupId=\"{group}\"\n  defaultValue=\"{}\"\n  values={{[\n",
+        tabs[0].1
+    );
+    for (label, value, _) in tabs {
+        block.push_str(&format!("    {{label: '{label}', value: '{value}'}},\n"));
+    }
+    block.push_str("  ]}>\n");
+    for (_, value, content) in tabs {
+        block.push_str(&format!(
+            "  <TabItem value=\"{value}\">\n\n{content}\n\n  </TabItem>\n"
+        ));
+    }
+    block.push_str("</Tabs>");
+    block
+}
+
+/// Extracts the regions of `source` named `tag.marker`, joined by newlines.
+///
+/// Markers are written with the line comment syntax of the language of `path`. The marker
+/// comments of nested regions and lines ending with a `DOCUSAURUS: hide` comment are dropped,
+/// then only the `tag.lines` of what remains are kept, if set. Blank lines at both ends of the
+/// result are removed, as well as the indentation common to all its lines if `tag.dedent` is
+/// set.
+pub fn extract_region(source: &str, path: &Path, tag: &LoadTag) -> Result<String, ErrorType> {
+    let syntax = MarkerSyntax::new(Language::comment_of(path));
+    let regions = syntax.parse_regions(source).map_err(|error| {
+        ErrorType::IncorrectRegion(IncorrectRegion {
+            filepath: tag.path.clone(),
+            error,
+        })
+    })?;
+    let lines_error = |reason| {
+        ErrorType::IncorrectLines(IncorrectLines {
+            marker: tag.marker.clone(),
+            lines: tag.lines.as_ref().map_or(String::new(), |range| {
+                format!("{}-{}", range.start(), range.end())
+            }),
+            reason,
+        })
+    };
+    match &tag.lines {
+        Some(range) if *range.start() == 0 => return Err(lines_error(LinesError::ZeroBased)),
+        Some(range) if range.start() > range.end() => {
+            return Err(lines_error(LinesError::Reversed))
+        }
+        _ => {}
+    }
+    let source_lines = source.lines().collect::<Vec<_>>();
+    let mut extracted = Vec::new();
+    for region in regions.iter().filter(|region| region.name == tag.marker) {
+        let mut lines = source_lines[region.start + 1..region.stop].to_vec();
+        lines.retain(|l| !syntax.is_marker_line(l) && !syntax.is_hidden_line(l));
+        if let Some(range) = &tag.lines {
+            if *range.end() > lines.len() {
+                return Err(lines_error(LinesError::PastEnd(lines.len())));
+            }
+            lines = lines[range.start() - 1..*range.end()].to_vec();
+        }
+
+        let mut region = lines.join("\n");
+        if tag.dedent {
+            region = remove_indent(&region).unwrap_or(region);
+        }
+        extracted.push(trim_blank_lines(&region).to_string());
+    }
+    if extracted.is_empty() {
+        return Err(ErrorType::IncorrectMarker(IncorrectMarker {
+            marker: tag.marker.clone(),
+            filepath: tag.path.clone(),
+        }));
+    }
+    Ok(extracted.join("\n"))
+}
+
+/// Removes the whitespace-only lines at both ends of `source`, and its trailing whitespace.
+fn trim_blank_lines(source: &str) -> &str {
+    let first_line = source
+        .lines()
+        .take_while(|l| l.trim().is_empty())
+        .map(|l| l.len() + 1)
+        .sum::<usize>();
+    source[first_line.min(source.len())..].trim_end()
+}
+
+/// Builds an error located at the byte `offset` of `source_text`.
+fn located_error(source_text: &str, offset: usize, error: ErrorType) -> InjectError {
+    let (line, column) = line_column(source_text, offset);
+    InjectError {
+        line,
+        column,
+        error,
+    }
+}
+
+/// Removes the indentation shared by every non-empty line of `source`.
+fn remove_indent(source: &str) -> Option<String> {
+    let min_indent = source
+        .lines()
+        .filter_map(|l| {
+            // Don't count empty lines
+            if !l.chars().any(|c| !c.is_whitespace()) {
+                return None;
+            }
+            Some(l.chars().take_while(|c| c.is_whitespace()).count())
+        })
+        .min()?;
+    if min_indent == 0 {
+        return Some(source.to_string());
+    }
+    let mut result = String::new();
+    for line in source.lines() {
+        result.extend(line.chars().skip(min_indent));
+        result.push('\n');
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, ops::RangeInclusive};
+
+    const EXAMPLE: &str = "\
+fn main() {
+    // DOCUSAURUS: region start
+    let a = 1;
+    // DOCUSAURUS: nested start
+    if a == 1 {
+        println!(\"one\");
+    }
+    // DOCUSAURUS: nested stop
+    assert_eq!(a, 1); // DOCUSAURUS: hide
+    let b = a + 1;
+    // DOCUSAURUS: region stop
+}
+";
+
+    fn tag(marker: &str, lines: Option<RangeInclusive<usize>>) -> LoadTag {
+        LoadTag {
+            path: "/example.rs".to_string(),
+            marker: marker.to_string(),
+            dedent: true,
+            lines,
+            output: false,
+            f64: false,
+            range: 0..0,
+        }
+    }
+
+    fn extract(tag: &LoadTag) -> Result<String, ErrorType> {
+        extract_region(EXAMPLE, Path::new("example.rs"), tag)
+    }
+
+    fn lines_error(lines: &str, reason: LinesError) -> ErrorType {
+        ErrorType::IncorrectLines(IncorrectLines {
+            marker: "region".to_string(),
+            lines: lines.to_string(),
+            reason,
+        })
+    }
+
+    #[test]
+    fn remove_indent_removes_the_common_indentation() {
+        assert_eq!(
+            remove_indent("    if a {\n\n        b();\n    }").as_deref(),
+            Some("if a {\n\n    b();\n}\n")
+        );
+        assert_eq!(remove_indent("a\n    b").as_deref(), Some("a\n    b"));
+        assert_eq!(remove_indent("  \n"), None);
+    }
+
+    #[test]
+    fn regions_drop_nested_markers_and_hidden_lines() {
+        assert_eq!(
+            extract(&tag("region", None)).unwrap(),
+            "let a = 1;\nif a == 1 {\n    println!(\"one\");\n}\nlet b = a + 1;"
+        );
+        let tag = LoadTag {
+            dedent: false,
+            ..tag("nested", None)
+        };
+        assert_eq!(
+            extract(&tag).unwrap(),
+            "    if a == 1 {\n        println!(\"one\");\n    }"
+        );
+    }
+
+    #[test]
+    fn lines_are_counted_after_stripping() {
+        assert_eq!(
+            extract(&tag("region", Some(2..=4))).unwrap(),
+            "if a == 1 {\n    println!(\"one\");\n}"
+        );
+        assert_eq!(
+            extract(&tag("region", Some(5..=5))).unwrap(),
+            "let b = a + 1;"
+        );
+    }
+
+    #[test]
+    fn invalid_lines_are_rejected() {
+        assert_eq!(
+            extract(&tag("region", Some(0..=2))),
+            Err(lines_error("0-2", LinesError::ZeroBased))
+        );
+        assert_eq!(
+            extract(&tag("region", Some(RangeInclusive::new(3, 2)))),
+            Err(lines_error("3-2", LinesError::Reversed))
+        );
+        assert_eq!(
+            extract(&tag("region", Some(2..=6))),
+            Err(lines_error("2-6", LinesError::PastEnd(5)))
+        );
+    }
+
+    #[test]
+    fn missing_markers_and_unbalanced_regions_are_rejected() {
+        assert_eq!(
+            extract(&tag("missing", None)),
+            Err(ErrorType::IncorrectMarker(IncorrectMarker {
+                marker: "missing".to_string(),
+                filepath: "/example.rs".to_string(),
+            }))
+        );
+        let source = "// DOCUSAURUS: region start\n";
+        assert_eq!(
+            extract_region(source, Path::new("example.rs"), &tag("region", None)),
+            Err(ErrorType::IncorrectRegion(IncorrectRegion {
+                filepath: "/example.rs".to_string(),
+                error: RegionError::NotStopped {
+                    marker: "region".to_string(),
+                    line: 1,
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn failed_tags_are_dropped_from_the_partial_result() {
+        let template = "before\n<load path='/example.rs' marker='region' lines='1' />\n\
+            <load path='/example.rs' marker='missing' />\nafter\n";
+        let dir = std::env::temp_dir().join(format!("inject_file_inject_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("example.rs");
+        fs::write(&path, EXAMPLE).unwrap();
+
+        let partial = inject_with_path(template, &Config::default(), |_| path.clone()).unwrap_err();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(partial.result, "before\nlet a = 1;\nafter\n");
+        assert_eq!(partial.errors.len(), 1);
+        assert_eq!(partial.errors[0].line, 3);
+        assert!(matches!(
+            partial.errors[0].error,
+            ErrorType::IncorrectMarker(_)
+        ));
+    }
+}
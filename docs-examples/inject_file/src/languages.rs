@@ -0,0 +1,52 @@
+//! The languages examples can be written in, and the comment syntax of their markers.
+
+use std::path::Path;
+
+/// A language examples can be written in.
+pub struct Language {
+    /// The name of the language in code fences.
+    pub fence: &'static str,
+    /// The extensions of the source files of this language.
+    pub extensions: &'static [&'static str],
+    /// The line comment syntax of this language.
+    pub comment: &'static str,
+}
+
+/// Every language whose examples can be injected.
+pub const LANGUAGES: &[Language] = &[
+    Language {
+        fence: "rust",
+        extensions: &["rs"],
+        comment: "//",
+    },
+    Language {
+        fence: "javascript",
+        extensions: &["js", "mjs"],
+        comment: "//",
+    },
+    Language {
+        fence: "typescript",
+        extensions: &["ts"],
+        comment: "//",
+    },
+    Language {
+        fence: "python",
+        extensions: &["py"],
+        comment: "#",
+    },
+];
+
+impl Language {
+    /// The language of the file at `path`, from its extension.
+    pub fn from_path(path: &Path) -> Option<&'static Language> {
+        let extension = path.extension()?.to_str()?;
+        LANGUAGES
+            .iter()
+            .find(|language| language.extensions.contains(&extension))
+    }
+
+    /// The line comment syntax of the file at `path`, `//` if its language is unknown.
+    pub fn comment_of(path: &Path) -> &'static str {
+        Self::from_path(path).map_or("//", |language| language.comment)
+    }
+}
@@ -0,0 +1,275 @@
+//! Injection of example regions into documentation templates, and checks of the templates.
+//!
+//! The `inject_file` binary is a thin command line interface over this library, so that other
+//! documentation sites can run the same injection and checks, e.g. from their own build
+//! scripts. Every operation returns a [`Report`] of what it did and of the problems it found,
+//! printed with [`Report::print`].
+
+// Failures are returned as `Diagnostic`s, built at most once per file: their size does not
+// matter.
+#![allow(clippy::result_large_err)]
+
+use inject::ErrorType;
+use similar::TextDiff;
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+use variables::{has_placeholders, Variables};
+
+pub mod api;
+mod cache;
+pub mod compile;
+pub mod diagnostics;
+pub mod directory;
+pub mod examples;
+mod fences;
+pub mod index;
+pub mod inject;
+pub mod languages;
+pub mod links;
+pub mod markers;
+mod outputs;
+pub mod precision;
+pub mod regions;
+mod rustdoc;
+mod scratch;
+pub mod tags;
+#[cfg(test)]
+mod testing;
+pub mod variables;
+pub mod watch;
+
+pub use diagnostics::{Diagnostic, DiagnosticKind, Format, Report};
+pub use inject::{inject_with_path, InjectError, PartialInjection};
+
+/// Options of the injection of templates, and layout of the examples they load.
+///
+/// The defaults match the layout of the parry user guide.
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// The directory `<load>` paths are relative to, or `None` for the [`Config::root_name`]
+    /// directory above each template.
+    pub root: Option<PathBuf>,
+    /// Name of the directory searched above each template when `root` is `None`.
+    pub root_name: String,
+    /// Path of the example a `<load-dims>` tag loads for each dimension, relative to the
+    /// root, with `{dim}` and `{name}` placeholders.
+    pub dims_path: String,
+    /// Manifest of the package the hand-written code fences of each dimension are compiled
+    /// with, relative to the root, with a `{dim}` placeholder.
+    pub fence_manifest: String,
+    /// The code compiled before the body of hand-written code fences, up to the opening of
+    /// their `main` function, with a `{dim}` placeholder.
+    pub fence_prelude: String,
+    /// The crates documented by the templates, whose items the snippets index lists. Their
+    /// `f64` variants are the same crates suffixed with `_f64` in code and `-f64` in manifests.
+    pub crates: Vec<String>,
+    /// Other crates whose items the templates name, e.g. re-exported by the documented ones.
+    pub extra_crates: Vec<String>,
+    /// Public items of the crates that their rustdoc JSON leaves out, accepted by
+    /// `check-api`, like the methods of the `impl dyn Shape` block of parry.
+    pub undocumented_items: Vec<String>,
+    /// The front matter and introduction of the snippets index page.
+    pub index_header: String,
+    /// Compare the outputs with the existing files instead of writing them.
+    pub check: bool,
+    /// Regenerate every output of a directory, even if its inputs did not change.
+    pub no_cache: bool,
+    /// Report the diff of each regenerated output.
+    pub print_diff: bool,
+    /// How reports are printed.
+    pub format: Format,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            root: None,
+            root_name: "docs-examples".to_string(),
+            dims_path: "/{dim}d/rust/examples/{name}{dim}.rs".to_string(),
+            fence_manifest: "{dim}d/rust/Cargo.toml".to_string(),
+            fence_prelude: "#![allow(unused)]
+use parry{dim}d::bounding_volume::*;
+use parry{dim}d::math::Real;
+use parry{dim}d::na::{self, *};
+use parry{dim}d::query::*;
+use parry{dim}d::shape::*;
+
+fn main() {
+"
+            .to_string(),
+            crates: vec!["parry2d".to_string(), "parry3d".to_string()],
+            extra_crates: vec!["nalgebra".to_string()],
+            undocumented_items: vec!["as_convex_polyhedron".to_string()],
+            index_header: "---
+id: snippets
+title: Snippets index
+sidebar_label: Snippets index
+---
+
+{/* Generated by inject_file from the examples, do not edit. */}
+
+Every example of this guide, with the types and functions of **Parry** it uses
+and the sections showing it. Search this page for an item, like
+`query::contact`, to find the examples using it.
+
+"
+            .to_string(),
+            check: false,
+            no_cache: false,
+            print_diff: false,
+            format: Format::default(),
+        }
+    }
+}
+
+impl Config {
+    /// The path of the example of dimension `dim` named `name` loaded by `<load-dims>` tags.
+    pub fn dims_example(&self, dim: u8, name: &str) -> String {
+        self.dims_path
+            .replace("{dim}", &dim.to_string())
+            .replace("{name}", name)
+    }
+}
+
+/// Reads the template at `input_path`, fills its `{{name}}` placeholders and injects its
+/// `<load>` tags.
+///
+/// Tag paths are resolved relative to the root of `config`, see [`template_root`]. Variables
+/// are read from the example packages of that directory.
+///
+/// On errors, the partial result keeps the placeholders of unknown variables and drops the
+/// tags that failed. It is empty if the template cannot be read or has no root.
+pub fn inject_file(input_path: &Path, config: &Config) -> Result<String, PartialInjection> {
+    let file_error = |error| PartialInjection {
+        result: String::new(),
+        errors: vec![InjectError {
+            line: 1,
+            column: 1,
+            error,
+        }],
+    };
+    let file = read_to_string(input_path)
+        .map_err(|error| file_error(ErrorType::IncorrectTemplate(error.to_string())))?;
+    let root = template_root(input_path, config)
+        .map_err(|error| file_error(ErrorType::MissingRoot(error)))?;
+    let (file, mut errors) = match Variables::load(&root) {
+        Ok(variables) => variables.substitute(&file),
+        // The placeholders are left as they are.
+        Err(error) if has_placeholders(&file) => (
+            file,
+            vec![InjectError {
+                line: 1,
+                column: 1,
+                error: ErrorType::IncorrectLock(error),
+            }],
+        ),
+        Err(_) => (file, Vec::new()),
+    };
+    match inject_with_path(&file, config, |file_path| resolve_path(&root, file_path)) {
+        Ok(result) if errors.is_empty() => Ok(result),
+        Ok(result) => Err(PartialInjection { result, errors }),
+        Err(partial) => {
+            errors.extend(partial.errors);
+            errors.sort_by_key(|error| (error.line, error.column));
+            Err(PartialInjection {
+                result: partial.result,
+                errors,
+            })
+        }
+    }
+}
+
+/// Injects the template at `input_path` and compares the result with `output_path`.
+///
+/// The report holds the unified diff of the output if it is out of date.
+pub fn check_file(input_path: &Path, output_path: &Path, config: &Config) -> Report {
+    let mut report = Report::default();
+    match inject_file(input_path, config) {
+        Ok(result) => {
+            if let Some(diff) = diff_output(input_path, output_path, &result) {
+                report.messages.push(diff);
+                report
+                    .diagnostics
+                    .push(out_of_date(input_path, output_path));
+            }
+        }
+        Err(partial) => report.diagnostics.extend(
+            partial
+                .errors
+                .iter()
+                .map(|error| Diagnostic::from_inject_error(input_path, error)),
+        ),
+    }
+    report
+}
+
+/// The directory `<load>` paths of the template at `input_path` are relative to: the root of
+/// `config` if set, or else the [`Config::root_name`] directory of the closest ancestor of
+/// `input_path` containing one.
+///
+/// The error tells why the root could not be found.
+pub fn template_root(input_path: &Path, config: &Config) -> Result<PathBuf, String> {
+    if let Some(root) = &config.root {
+        return Ok(root.clone());
+    }
+    let input_path = input_path
+        .canonicalize()
+        .map_err(|error| format!("could not read {}: {error}", input_path.display()))?;
+    input_path
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join(&config.root_name))
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| {
+            format!(
+                "could not find a {} directory above {}, use --root to set it",
+                config.root_name,
+                input_path.display()
+            )
+        })
+}
+
+/// The diagnostic that [`template_root`] found no root for `template`, because of `message`.
+fn missing_root(template: &Path, message: String) -> Diagnostic {
+    Diagnostic::in_file(template, DiagnosticKind::MissingRoot, message)
+}
+
+/// Resolves the path of a `<load>` tag, relative to `root`.
+pub fn resolve_path(root: &Path, file_path: &str) -> PathBuf {
+    root.join(file_path.trim_start_matches('/'))
+}
+
+/// The unified diff from the content of `output_path` to `result`, or `None` if they are
+/// equal.
+///
+/// A missing output is diffed against an empty file.
+fn diff_output(input_path: &Path, output_path: &Path, result: &str) -> Option<String> {
+    let existing = read_to_string(output_path).unwrap_or_default();
+    if existing == result {
+        return None;
+    }
+    let diff = TextDiff::from_lines(existing.as_str(), result);
+    let diff = diff
+        .unified_diff()
+        .header(
+            &output_path.display().to_string(),
+            &input_path.display().to_string(),
+        )
+        .to_string();
+    Some(diff.trim_end_matches('\n').to_string())
+}
+
+/// The diagnostic that the generated `output_path` does not match its template.
+fn out_of_date(input_path: &Path, output_path: &Path) -> Diagnostic {
+    Diagnostic {
+        file: output_path.display().to_string(),
+        line: None,
+        column: None,
+        kind: DiagnosticKind::OutOfDate,
+        path: Some(input_path.display().to_string()),
+        marker: None,
+        message: format!("not up to date with {}", input_path.display()),
+    }
+}
@@ -0,0 +1,249 @@
+//! Verification of the internal links of templates.
+
+use crate::diagnostics::{line_column, Diagnostic, DiagnosticKind, Report};
+use crate::directory::{collect_templates, read_template};
+use crate::fences::find_fences;
+use regex::Regex;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+/// Checks the links of every template under `templates_dir`.
+///
+/// Anchors, e.g. `#ray-casting`, must match a heading of their page, relative `.mdx` links
+/// another template, and `/img/*` paths a file under `static_dir`. External links and other
+/// site routes are not checked. Links in code fences are ignored.
+pub fn check_links(templates_dir: &Path, static_dir: &Path) -> Report {
+    let re_link = Regex::new(r"\]\(([^)]*)\)").unwrap();
+    let mut anchors = HashMap::new();
+    let mut report = Report::default();
+    let templates = match collect_templates(templates_dir) {
+        Ok(templates) => templates,
+        Err(diagnostic) => return diagnostic.into(),
+    };
+
+    for template in templates {
+        let content = match read_template(&template) {
+            Ok(content) => content,
+            Err(diagnostic) => {
+                report.diagnostics.push(diagnostic);
+                continue;
+            }
+        };
+        let code_lines = code_lines(&content);
+        for link in re_link.captures_iter(&content) {
+            let target = link.get(1).unwrap();
+            let (line, column) = line_column(&content, target.start());
+            if code_lines.contains(&(line - 1)) {
+                continue;
+            }
+            let Err(reason) = check_target(target.as_str(), &template, static_dir, &mut anchors)
+            else {
+                continue;
+            };
+            report.diagnostics.push(Diagnostic {
+                file: template.display().to_string(),
+                line: Some(line),
+                column: Some(column),
+                kind: DiagnosticKind::BrokenLink,
+                path: Some(target.as_str().to_string()),
+                marker: None,
+                message: format!("broken link to `{}`: {reason}", target.as_str()),
+            });
+        }
+    }
+
+    report.success = Some("✅ No broken links.".to_string());
+    report.failure = Some(format!("❌ {} broken link(s).", report.diagnostics.len()));
+    report
+}
+
+/// Checks the `target` of a link of `template`.
+///
+/// `anchors` caches the heading anchors of the pages already read, by path.
+fn check_target(
+    target: &str,
+    template: &Path,
+    static_dir: &Path,
+    anchors: &mut HashMap<PathBuf, HashSet<String>>,
+) -> Result<(), String> {
+    // A link may have a title, as in `[text](target "title")`.
+    let target = match target.split_once(char::is_whitespace) {
+        Some((target, title)) if title.trim().starts_with('"') => target,
+        Some(_) => return Err("it contains whitespace".to_string()),
+        None => target,
+    };
+    if target.contains("://") || target.starts_with("mailto:") {
+        return Ok(());
+    }
+    let (path, anchor) = target.split_once('#').unwrap_or((target, ""));
+
+    if let Some(image) = path.strip_prefix("/img/") {
+        return if static_dir.join("img").join(image).is_file() {
+            Ok(())
+        } else {
+            Err(format!("it is not in {}", static_dir.display()))
+        };
+    }
+    if path.starts_with('/') {
+        // Other site routes are generated by Docusaurus.
+        return Ok(());
+    }
+
+    let page = if path.is_empty() {
+        template.to_path_buf()
+    } else {
+        template
+            .parent()
+            .unwrap()
+            .join(path.trim_start_matches("./"))
+    };
+    if !page.is_file() {
+        return Err("this file does not exist".to_string());
+    }
+    if anchor.is_empty() {
+        return Ok(());
+    }
+    let page_anchors = anchors.entry(page.clone()).or_insert_with(|| {
+        // An unreadable page has no anchors, and is reported when checking its own links.
+        heading_anchors(&read_template(&page).unwrap_or_default())
+    });
+    if !page_anchors.contains(anchor) {
+        return Err(format!("no heading of {} has this anchor", page.display()));
+    }
+    Ok(())
+}
+
+/// The indices of the lines of `template` belonging to code fences, starting at 0.
+fn code_lines(template: &str) -> HashSet<usize> {
+    find_fences(template)
+        .iter()
+        .flat_map(|fence| fence.open..fence.open + fence.body.lines().count() + 2)
+        .collect()
+}
+
+/// A heading of a template.
+pub struct Heading {
+    /// Index of the line of the heading, starting at 0.
+    pub line: usize,
+    /// The text of the heading, without its markup.
+    pub text: String,
+    /// The anchor of the heading, as generated by Docusaurus.
+    pub anchor: String,
+}
+
+/// The headings of `template`, in order.
+///
+/// A heading can set its anchor explicitly with a trailing `{#anchor}`. Otherwise it is the
+/// slug of its text, suffixed with `-1`, `-2`, etc. for repeated headings.
+pub fn headings(template: &str) -> Vec<Heading> {
+    let re_heading = Regex::new(r"^#{1,6}\s+(.*?)\s*(?:\{#([^}]+)\})?\s*$").unwrap();
+    let re_html = Regex::new(r"<[^>]*>").unwrap();
+    let re_link = Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap();
+    let code_lines = code_lines(template);
+    let mut headings = Vec::new();
+    let mut occurrences = HashMap::new();
+
+    for (i, line) in template.lines().enumerate() {
+        if code_lines.contains(&i) {
+            continue;
+        }
+        let Some(heading) = re_heading.captures(line) else {
+            continue;
+        };
+        let text = re_html.replace_all(&heading[1], "");
+        let text = re_link.replace_all(&text, "$1").trim().to_string();
+        let anchor = match heading.get(2) {
+            Some(anchor) => anchor.as_str().to_string(),
+            None => {
+                let slug = slug(&text);
+                let occurrence = occurrences.entry(slug.clone()).or_insert(0);
+                let anchor = match *occurrence {
+                    0 => slug,
+                    n => format!("{slug}-{n}"),
+                };
+                *occurrence += 1;
+                anchor
+            }
+        };
+        headings.push(Heading {
+            line: i,
+            text,
+            anchor,
+        });
+    }
+    headings
+}
+
+/// The anchors of the headings of `template`.
+fn heading_anchors(template: &str) -> HashSet<String> {
+    headings(template)
+        .into_iter()
+        .map(|heading| heading.anchor)
+        .collect()
+}
+
+/// The GitHub-style slug of a heading text: lowercased, without punctuation, and with spaces
+/// replaced by `-`.
+fn slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            _ if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_match_docusaurus() {
+        assert_eq!(slug("Ray casting"), "ray-casting");
+        assert_eq!(slug("  Time of impact (TOI)"), "time-of-impact-toi");
+        assert_eq!(
+            slug("The `Shape` trait: a primer"),
+            "the-shape-trait-a-primer"
+        );
+        assert_eq!(
+            slug("snake_case and kebab-case"),
+            "snake_case-and-kebab-case"
+        );
+        assert_eq!(slug("Énergie cinétique"), "énergie-cinétique");
+    }
+
+    #[test]
+    fn headings_have_unique_anchors() {
+        let template = "\
+# Shapes
+## Example
+```rust
+## Not a heading
+```
+## Example
+### <b>Bold</b> [link](#shapes) {#custom}
+## Example
+";
+        let headings = headings(template)
+            .into_iter()
+            .map(|heading| (heading.line, heading.text, heading.anchor))
+            .collect::<Vec<_>>();
+        let heading = |line, text: &str, anchor: &str| (line, text.to_string(), anchor.to_string());
+        assert_eq!(
+            headings,
+            vec![
+                heading(0, "Shapes", "shapes"),
+                heading(1, "Example", "example"),
+                heading(5, "Example", "example-1"),
+                heading(6, "Bold link", "custom"),
+                heading(7, "Example", "example-2"),
+            ]
+        );
+    }
+}
@@ -1,42 +1,139 @@
-use std::{fs::read_to_string, process::ExitCode};
-use strinject::*;
+use inject_file::{
+    api, check_file, compile, directory, index, inject_file, links, markers, precision, watch,
+    Config, Diagnostic, Format, Report,
+};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
 
 fn main() -> ExitCode {
-    let args = std::env::args().collect::<Vec<_>>();
-    let input_path = args
-        .get(1)
-        .expect("Expected 1 argument: the path to the file to parse.");
-    let file: &str = &read_to_string(input_path)
-        .unwrap_or_else(|_| panic!("Could not read file at input {}", input_path));
-
-    let result = inject_with_path(file, |file_path| {
-        let mut path = "..".to_string();
-        path.push_str(file_path);
-        path.to_string()
-    });
-    match result {
+    let mut config = Config::default();
+    let mut watch = false;
+    let mut paths = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => config.check = true,
+            "--watch" => watch = true,
+            "--no-cache" => config.no_cache = true,
+            "--print-diff" => config.print_diff = true,
+            "--format" => {
+                config.format =
+                    Format::parse(&args.next().expect("Expected a value after --format."))
+                        .unwrap_or_else(|error| panic!("{error}"))
+            }
+            "--root" => {
+                config.root = Some(PathBuf::from(
+                    args.next().expect("Expected a directory after --root."),
+                ))
+            }
+            _ => paths.push(arg),
+        }
+    }
+
+    if paths.first().map(String::as_str) == Some("snippets-index") {
+        let [_, templates_dir, output_path] = &paths[..] else {
+            panic!("Expected 2 arguments with snippets-index: the templates directory and the output file.");
+        };
+        let report =
+            index::write_snippets_index(Path::new(templates_dir), Path::new(output_path), &config);
+        return exit(&report, config.format);
+    }
+
+    if paths.first().map(String::as_str) == Some("check-markers") {
+        let [_, templates_dir, examples_dir] = &paths[..] else {
+            panic!("Expected 2 arguments with check-markers: the templates directory and the examples directory.");
+        };
+        let report =
+            markers::check_markers(Path::new(templates_dir), Path::new(examples_dir), &config);
+        return exit(&report, config.format);
+    }
+
+    if paths.first().map(String::as_str) == Some("check-api") {
+        let [_, templates_dir, examples_dir] = &paths[..] else {
+            panic!("Expected 2 arguments with check-api: the templates directory and the examples directory.");
+        };
+        let report = api::check_api(Path::new(templates_dir), Path::new(examples_dir), &config);
+        return exit(&report, config.format);
+    }
+
+    if paths.first().map(String::as_str) == Some("check-links") {
+        let [_, templates_dir, static_dir] = &paths[..] else {
+            panic!("Expected 2 arguments with check-links: the templates directory and the static directory.");
+        };
+        let report = links::check_links(Path::new(templates_dir), Path::new(static_dir));
+        return exit(&report, config.format);
+    }
+
+    if paths.first().map(String::as_str) == Some("check-fences") {
+        let [_, templates_dir] = &paths[..] else {
+            panic!("Expected 1 argument with check-fences: the templates directory.");
+        };
+        let report = compile::check_fences(Path::new(templates_dir), &config);
+        return exit(&report, config.format);
+    }
+
+    if paths.first().map(String::as_str) == Some("check-f64") {
+        let [_, templates_dir] = &paths[..] else {
+            panic!("Expected 1 argument with check-f64: the templates directory.");
+        };
+        let report = precision::check_f64(Path::new(templates_dir), &config);
+        return exit(&report, config.format);
+    }
+
+    let input_path = Path::new(
+        paths
+            .first()
+            .expect("Expected 1 argument: the path to the file or directory to parse."),
+    );
+
+    if input_path.is_dir() {
+        let output_dir = Path::new(paths.get(1).expect(
+            "Expected 2 arguments with a directory: the templates directory and the output directory.",
+        ));
+        if watch {
+            assert!(!config.check, "--watch cannot be combined with --check.");
+            watch::watch_directory(input_path, output_dir, &config);
+        }
+        let report = directory::inject_directory(input_path, output_dir, &config);
+        return exit(&report, config.format);
+    }
+    assert!(!watch, "--watch expects a templates directory.");
+
+    if config.check {
+        let output_path =
+            Path::new(paths.get(1).expect(
+                "Expected 2 arguments with --check: the template and its generated output.",
+            ));
+        return exit(&check_file(input_path, output_path, &config), config.format);
+    }
+    match inject_file(input_path, &config) {
         Ok(result) => {
             print!("{result}");
+            ExitCode::from(0)
         }
-        Err(error) => {
-            for e in error.errors {
-                match e {
-                    ErrorType::IncorrectTag => {
-                        eprintln!("❌ ERROR: a <load> tag was incorrectly written in {}, maybe missing path or marker, or not using `'` for string delimiters ?", input_path);
-                    }
-                    ErrorType::IncorrectPath(path) => {
-                        eprintln!("❌ ERROR: path {} not found", path);
-                    }
-                    ErrorType::IncorrectMarker(IncorrectMarker { marker, filepath }) => {
-                        eprintln!("❌ ERROR: marker {} not found in {}", marker, filepath);
-                    }
-                }
-            }
-            if let Some(result) = error.result {
-                print!("{result}");
-            }
-            return ExitCode::from(1);
+        Err(partial) => {
+            // The partial result still goes to stdout, which scripts redirect to the output.
+            print!("{}", partial.result);
+            let report = Report {
+                diagnostics: partial
+                    .errors
+                    .iter()
+                    .map(|error| Diagnostic::from_inject_error(input_path, error))
+                    .collect(),
+                ..Report::default()
+            };
+            exit(&report, config.format)
         }
     }
-    ExitCode::from(0)
+}
+
+/// Prints `report` in `format`, and exits with a non-zero code if it has problems.
+fn exit(report: &Report, format: Format) -> ExitCode {
+    if report.print(format) {
+        ExitCode::from(0)
+    } else {
+        ExitCode::from(1)
+    }
 }
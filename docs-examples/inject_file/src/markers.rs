@@ -0,0 +1,262 @@
+//! Verification that every marker of the examples is loaded by a template.
+
+use crate::diagnostics::{Diagnostic, DiagnosticKind, Report};
+use crate::directory::{collect_templates, read_dir, read_template};
+use crate::languages::{Language, LANGUAGES};
+use crate::regions::{MarkerSyntax, Region};
+use crate::tags::load_tags;
+use crate::{missing_root, resolve_path, template_root, Config};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Cross-references the markers defined under `examples_dir` with the `<load>` tags of the
+/// templates under `templates_dir`.
+///
+/// Reports markers that no template loads, markers defined several times in the same file, and
+/// files whose markers are unbalanced or overlap.
+pub fn check_markers(templates_dir: &Path, examples_dir: &Path, config: &Config) -> Report {
+    let mut report = Report::default();
+    let templates = match collect_templates(templates_dir) {
+        Ok(templates) => templates,
+        Err(diagnostic) => return diagnostic.into(),
+    };
+    let mut loaded = HashSet::new();
+    for template in templates {
+        let content = match read_template(&template) {
+            Ok(content) => content,
+            Err(diagnostic) => {
+                report.diagnostics.push(diagnostic);
+                continue;
+            }
+        };
+        let root = match template_root(&template, config) {
+            Ok(root) => root,
+            Err(message) => {
+                report.diagnostics.push(missing_root(&template, message));
+                continue;
+            }
+        };
+        for tag in load_tags(&content, config) {
+            // Tags pointing to missing files are reported by the injection itself.
+            if let Ok(path) = fs::canonicalize(resolve_path(&root, &tag.path)) {
+                loaded.insert((path, tag.marker));
+            }
+        }
+    }
+
+    let mut sources = Vec::new();
+    let in_examples = examples_dir.file_name() == Some("examples".as_ref());
+    if let Err(diagnostic) = collect_sources(examples_dir, in_examples, &mut sources) {
+        report.diagnostics.push(diagnostic);
+        return report;
+    }
+
+    for source in sources {
+        let content = fs::read_to_string(&source).unwrap_or_default();
+        let canonical_path = fs::canonicalize(&source).unwrap_or(source.clone());
+        let syntax = MarkerSyntax::new(Language::comment_of(&source));
+        let regions = match syntax.parse_regions(&content) {
+            Ok(regions) => regions,
+            Err(error) => {
+                report.diagnostics.push(marker_diagnostic(
+                    &source,
+                    error.marker(),
+                    error.line(),
+                    DiagnosticKind::IncorrectRegion,
+                    error.to_string(),
+                ));
+                continue;
+            }
+        };
+        let mut first_definitions: HashMap<&str, &Region> = HashMap::new();
+
+        for region in &regions {
+            if let Some(first) = first_definitions.get(region.name.as_str()) {
+                report.diagnostics.push(marker_diagnostic(
+                    &source,
+                    &region.name,
+                    region.start + 1,
+                    DiagnosticKind::DuplicatedMarker,
+                    format!(
+                        "marker {} is already defined at line {}",
+                        region.name,
+                        first.start + 1
+                    ),
+                ));
+                continue;
+            }
+            first_definitions.insert(&region.name, region);
+
+            if !loaded.contains(&(canonical_path.clone(), region.name.clone())) {
+                report.diagnostics.push(marker_diagnostic(
+                    &source,
+                    &region.name,
+                    region.start + 1,
+                    DiagnosticKind::UnusedMarker,
+                    format!("marker {} is not loaded by any template", region.name),
+                ));
+            }
+        }
+    }
+
+    report.failure = Some(format!(
+        "❌ {} orphaned, duplicated or incorrect marker(s).",
+        report.diagnostics.len()
+    ));
+    report
+}
+
+fn marker_diagnostic(
+    source: &Path,
+    marker: &str,
+    line: usize,
+    kind: DiagnosticKind,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        file: source.display().to_string(),
+        line: Some(line),
+        column: None,
+        kind,
+        path: None,
+        marker: Some(marker.to_string()),
+        message,
+    }
+}
+
+/// Recursively collects the example sources under `dir`, in any of the [`LANGUAGES`], sorted
+/// by path.
+///
+/// Only the files under an `examples` directory are examples: the sources of the tools, like
+/// the fixtures of the tests of this crate, are skipped, as are build and hidden directories.
+fn collect_sources(
+    dir: &Path,
+    in_examples: bool,
+    sources: &mut Vec<PathBuf>,
+) -> Result<(), Diagnostic> {
+    let mut entries = read_dir(dir)?;
+    entries.sort();
+    for path in entries {
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        if path.is_dir() {
+            if file_name != "target" && !file_name.starts_with('.') {
+                collect_sources(&path, in_examples || file_name == "examples", sources)?;
+            }
+        } else if in_examples
+            && LANGUAGES.iter().any(|language| {
+                path.extension().is_some_and(|ext| {
+                    language
+                        .extensions
+                        .contains(&ext.to_str().unwrap_or_default())
+                })
+            })
+        {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the markers of the `example`, loaded by the `template`, in a fresh directory
+    /// that also holds the `source` of a tool.
+    fn check(
+        name: &str,
+        template: &str,
+        example: &str,
+        source: &str,
+    ) -> Vec<(DiagnosticKind, String, usize)> {
+        let dir = std::env::temp_dir()
+            .join("inject_file_markers")
+            .join(format!("{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("templates")).unwrap();
+        fs::create_dir_all(dir.join("rust/examples")).unwrap();
+        fs::create_dir_all(dir.join("tool/src")).unwrap();
+        fs::write(dir.join("templates/page.mdx"), template).unwrap();
+        fs::write(dir.join("rust/examples/example.rs"), example).unwrap();
+        fs::write(dir.join("tool/src/lib.rs"), source).unwrap();
+        let config = Config {
+            root: Some(dir.clone()),
+            ..Config::default()
+        };
+
+        let report = check_markers(&dir.join("templates"), &dir, &config);
+        fs::remove_dir_all(&dir).unwrap();
+        report
+            .diagnostics
+            .into_iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.kind,
+                    diagnostic.marker.unwrap(),
+                    diagnostic.line.unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn loaded_markers_are_not_reported() {
+        let template = "<load path='/rust/examples/example.rs' marker='a' />\n\
+            <load path='/rust/examples/example.rs' marker='b' />\n";
+        let example = "\
+// DOCUSAURUS: a start
+// DOCUSAURUS: b start
+let b = 1;
+// DOCUSAURUS: b stop
+// DOCUSAURUS: a stop
+";
+        assert_eq!(check("loaded", template, example, ""), vec![]);
+    }
+
+    #[test]
+    fn unused_and_duplicated_markers_are_reported() {
+        let template = "<load path='/rust/examples/example.rs' marker='a' />\n";
+        let example = "\
+// DOCUSAURUS: a start
+// DOCUSAURUS: a stop
+// DOCUSAURUS: unused start
+// DOCUSAURUS: unused stop
+// DOCUSAURUS: a start
+// DOCUSAURUS: a stop
+";
+        assert_eq!(
+            check("unused", template, example, ""),
+            vec![
+                (DiagnosticKind::UnusedMarker, "unused".to_string(), 3),
+                (DiagnosticKind::DuplicatedMarker, "a".to_string(), 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn unbalanced_markers_are_reported() {
+        let template = "<load path='/rust/examples/example.rs' marker='a' />\n";
+        let example = "\
+// DOCUSAURUS: a start
+// DOCUSAURUS: b start
+// DOCUSAURUS: a stop
+// DOCUSAURUS: b stop
+";
+        assert_eq!(
+            check("unbalanced", template, example, ""),
+            vec![(DiagnosticKind::IncorrectRegion, "a".to_string(), 3)]
+        );
+    }
+
+    #[test]
+    fn markers_outside_of_examples_are_skipped() {
+        let source = "\
+// DOCUSAURUS: fixture start
+// DOCUSAURUS: fixture stop
+";
+        assert_eq!(check("skipped", "", "", source), vec![]);
+    }
+}
@@ -0,0 +1,107 @@
+//! Runs of the cargo examples whose output the templates load, and the modules they declare.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+/// Builds and runs the cargo example at `path`, returning what it prints to stdout.
+///
+/// The example is run in release mode from the package whose `Cargo.toml` is the closest
+/// one above `path`. The error is the end of the output of cargo if the example fails to build
+/// or panics.
+pub fn run_example(path: &Path) -> Result<String, String> {
+    let output = run_example_process(path)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lines = stderr.lines().collect::<Vec<_>>();
+        let last_lines = &lines[lines.len().saturating_sub(5)..];
+        return Err(format!(
+            "it failed with {}:\n{}",
+            output.status,
+            last_lines.join("\n")
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).replace("\r\n", "\n"))
+}
+
+/// Builds and runs the cargo example at `path` in release mode, returning its raw output.
+fn run_example_process(path: &Path) -> Result<Output, String> {
+    let (Some(name), Some(examples_dir)) = (path.file_stem(), path.parent()) else {
+        return Err("not a cargo example".to_string());
+    };
+    if path.extension().is_none_or(|ext| ext != "rs") || !examples_dir.ends_with("examples") {
+        return Err("only the examples of cargo packages can be run".to_string());
+    }
+    let Some(manifest) = examples_dir
+        .ancestors()
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| manifest.is_file())
+    else {
+        return Err("no Cargo.toml found above it".to_string());
+    };
+    run_cargo_example(&manifest, &name.to_string_lossy(), true)
+}
+
+/// Builds and runs the example target `name` of the package of `manifest`, returning its raw
+/// output.
+///
+/// Unless `release` is set, the example is built with the `dev` profile, reusing what
+/// `cargo test` and `cargo clippy` already built. It fails if the `Cargo.lock` of the package
+/// is out of date, like `cargo test --locked` in CI.
+pub fn run_cargo_example(manifest: &Path, name: &str, release: bool) -> Result<Output, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    Command::new(cargo)
+        .args(["run", "--quiet", "--locked"])
+        .args(release.then_some("--release"))
+        .arg("--manifest-path")
+        .arg(manifest)
+        .arg("--example")
+        .arg(name)
+        .env("RUST_BACKTRACE", "0")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|error| format!("could not run cargo: {error}"))
+}
+
+/// The files of the modules that the Rust source at `path` declares with `mod <name>;`, and
+/// of the modules they declare in turn.
+///
+/// `path` is resolved like the root of a crate, e.g. an example: its `mod scenarios;` is
+/// `scenarios.rs` or `scenarios/mod.rs` next to it. Modules whose file does not exist are left
+/// out, cargo reports them.
+pub fn module_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    if let Some(dir) = path.parent() {
+        collect_module_files(path, dir, &mut files);
+    }
+    files
+}
+
+/// Adds to `files` the modules declared by the source at `path`, resolved in `dir`.
+fn collect_module_files(path: &Path, dir: &Path, files: &mut Vec<PathBuf>) {
+    let source = fs::read_to_string(path).unwrap_or_default();
+    for line in source.lines() {
+        let line = line.trim();
+        let line = line
+            .strip_prefix("pub ")
+            .or_else(|| line.strip_prefix("pub(crate) "))
+            .unwrap_or(line);
+        let Some(name) = line.strip_prefix("mod ").and_then(|l| l.strip_suffix(';')) else {
+            continue;
+        };
+        let candidates = [
+            (dir.join(format!("{name}.rs")), dir.join(name)),
+            (dir.join(name).join("mod.rs"), dir.join(name)),
+        ];
+        let Some((file, module_dir)) = candidates.into_iter().find(|(file, _)| file.is_file())
+        else {
+            continue;
+        };
+        if !files.contains(&file) {
+            files.push(file.clone());
+            collect_module_files(&file, &module_dir, files);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+//! Generation of the `f64` variant of examples, written against the `f32` crates.
+
+use crate::diagnostics::{Diagnostic, DiagnosticKind, Report};
+use crate::directory::{collect_templates, read_template};
+use crate::outputs::module_files;
+use crate::scratch::{
+    absolute_dependency_paths, cargo, copy_lockfile, example_package, scratch_package, write_file,
+};
+use crate::tags::load_tags;
+use crate::{missing_root, resolve_path, template_root, Config};
+use regex::{Captures, Regex};
+use std::{collections::BTreeSet, fs, path::Path};
+
+/// Rewrites Rust code using the `crates`, e.g. `parry2d`, and `f32` to use their `f64`
+/// variants, e.g. `parry2d_f64`, and `f64`, including float suffixes like `1.0f32`.
+pub fn to_f64(source: &str, crates: &[String]) -> String {
+    let re_crate = Regex::new(&format!(r"\b({})\b", alternatives(crates))).unwrap();
+    let re_float = Regex::new(r"(^|[^\w])f32\b").unwrap();
+    // Float suffixes like `1.0f32` are preceded by a digit, which `\b` does not allow.
+    let re_suffix = Regex::new(r"([0-9_])f32\b").unwrap();
+    let source = re_crate.replace_all(source, "${1}_f64");
+    let source = re_float.replace_all(&source, "${1}f64");
+    re_suffix.replace_all(&source, "${1}f64").into_owned()
+}
+
+/// Rewrites a `Cargo.toml` depending on the `crates` to depend on their `f64` variants, e.g.
+/// `parry2d-f64`.
+fn to_f64_manifest(manifest: &str, crates: &[String]) -> String {
+    let re_crate = Regex::new(&format!(r"(?m)^({})(\s*=)", alternatives(crates))).unwrap();
+    re_crate.replace_all(manifest, "${1}-f64${2}").into_owned()
+}
+
+/// Rewrites the path dependencies of a `Cargo.toml` that have an `f64` sibling, e.g. the
+/// `scenarios-f64` package next to `scenarios`, to depend on the sibling instead, renamed so
+/// that the code using them is unchanged.
+///
+/// The paths of the dependencies must be absolute, see [`absolute_dependency_paths`].
+fn to_f64_path_dependencies(manifest: &str) -> String {
+    let re_dependency =
+        Regex::new(r#"(?m)^([\w-]+)\s*=\s*\{[^}\n]*\bpath\s*=\s*"([^"]+)"[^}\n]*\}"#).unwrap();
+    re_dependency
+        .replace_all(manifest, |dependency: &Captures| {
+            let (name, path) = (&dependency[1], &dependency[2]);
+            let f64_path = format!("{path}-f64");
+            if !Path::new(&f64_path).join("Cargo.toml").is_file() {
+                return dependency[0].to_string();
+            }
+            format!("{name} = {{ package = \"{name}-f64\", path = \"{f64_path}\" }}")
+        })
+        .into_owned()
+}
+
+/// The regex matching any of the `names`.
+fn alternatives(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| regex::escape(name))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Checks that the `f64` variant of every Rust example loaded by the templates under
+/// `templates_dir` compiles against the `f64` crates, so that any of their tags can take
+/// `f64='true'`.
+///
+/// Each example is rewritten with [`to_f64`], with the modules it declares, to a scratch copy
+/// of its package depending on the `f64` crates, and on the `-f64` siblings of its path
+/// dependencies, and checked with `cargo check --example`.
+pub fn check_f64(templates_dir: &Path, config: &Config) -> Report {
+    let mut report = Report::default();
+    let templates = match collect_templates(templates_dir) {
+        Ok(templates) => templates,
+        Err(diagnostic) => return diagnostic.into(),
+    };
+    let mut examples = BTreeSet::new();
+    for template in templates {
+        let content = match read_template(&template) {
+            Ok(content) => content,
+            Err(diagnostic) => {
+                report.diagnostics.push(diagnostic);
+                continue;
+            }
+        };
+        let root = match template_root(&template, config) {
+            Ok(root) => root,
+            Err(message) => {
+                report.diagnostics.push(missing_root(&template, message));
+                continue;
+            }
+        };
+        for tag in load_tags(&content, config) {
+            let path = resolve_path(&root, &tag.path);
+            // Tags pointing to missing files are reported by the injection itself.
+            if path.is_file() && path.extension().is_some_and(|ext| ext == "rs") {
+                examples.insert(path);
+            }
+        }
+    }
+
+    for example in &examples {
+        if let Err(message) = check_example(example, &config.crates) {
+            report.diagnostics.push(Diagnostic {
+                file: example.display().to_string(),
+                line: None,
+                column: None,
+                kind: DiagnosticKind::F64Compilation,
+                path: None,
+                marker: None,
+                message,
+            });
+        }
+    }
+
+    report.success = Some(format!(
+        "✅ {} example(s) compile with f64.",
+        examples.len()
+    ));
+    report.failure = Some(format!(
+        "❌ {} example(s) do not compile with f64.",
+        report.diagnostics.len()
+    ));
+    report
+}
+
+/// Writes the `f64` variant of `example`, and of its modules, to a copy of its package, and
+/// checks it compiles.
+fn check_example(example: &Path, crates: &[String]) -> Result<(), String> {
+    let package_dir = example_package(example)?;
+    let name = example.file_stem().unwrap().to_string_lossy();
+    let manifest = fs::read_to_string(package_dir.join("Cargo.toml"))
+        .map_err(|error| format!("could not read its Cargo.toml: {error}"))?;
+    let f64_dir = scratch_package(
+        &format!("{}_f64", package_dir.display()).replace(['/', '\\', '.'], "_"),
+        &to_f64_path_dependencies(&to_f64_manifest(
+            &absolute_dependency_paths(&manifest, package_dir),
+            crates,
+        )),
+    )?;
+    copy_lockfile(package_dir, &f64_dir)?;
+    for file in [example.to_path_buf()]
+        .into_iter()
+        .chain(module_files(example))
+    {
+        let source = fs::read_to_string(&file)
+            .map_err(|error| format!("could not read {}: {error}", file.display()))?;
+        write_file(
+            &f64_dir.join(file.strip_prefix(package_dir).unwrap()),
+            &to_f64(&source, crates),
+        )?;
+    }
+
+    let output = cargo(
+        &f64_dir,
+        &[
+            "check",
+            "--quiet",
+            "--message-format",
+            "short",
+            "--example",
+            &name,
+        ],
+    )?;
+    if !output.status.success() {
+        return Err(format!(
+            "its f64 variant does not compile:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scratch::absolute_dependency_paths;
+    use crate::testing::TempDir;
+
+    fn to_f64(source: &str) -> String {
+        super::to_f64(source, &["parry2d".to_string(), "parry3d".to_string()])
+    }
+
+    #[test]
+    fn crates_and_floats_are_rewritten() {
+        assert_eq!(
+            to_f64("use parry3d::math::Real;\nlet x: f32 = 1.0f32 + 2_f32;"),
+            "use parry3d_f64::math::Real;\nlet x: f64 = 1.0f64 + 2_f64;"
+        );
+        assert_eq!(
+            to_f64("let v = Vec::<f32>::new(); f32::consts::PI; parry2d::shape::Ball"),
+            "let v = Vec::<f64>::new(); f64::consts::PI; parry2d_f64::shape::Ball"
+        );
+    }
+
+    #[test]
+    fn identifiers_are_left_unchanged() {
+        let source = "let my_f32x = parry2d_f64::Ball; let parry2dx = nalgebra::f32x4;";
+        assert_eq!(to_f64(source), source);
+    }
+
+    #[test]
+    fn manifests_depend_on_the_f64_crates() {
+        let crates = ["parry2d".to_string()];
+        assert_eq!(
+            super::to_f64_manifest("parry2d = \"0.17\"\nparry2d_extra = \"1\"\n", &crates),
+            "parry2d-f64 = \"0.17\"\nparry2d_extra = \"1\"\n"
+        );
+    }
+
+    #[test]
+    fn path_dependencies_use_their_f64_sibling() {
+        let dir = TempDir::new("path_dependencies_use_their_f64_sibling");
+        dir.write("shared/Cargo.toml", "");
+        dir.write("shared-f64/Cargo.toml", "");
+        dir.write("other/Cargo.toml", "");
+        dir.write("package/Cargo.toml", "");
+        let manifest = "\
+[dependencies]
+shared = { path = \"../shared\" }
+other = { path = \"../other\", features = [\"a\"] }
+
+[[example]]
+name = \"example\"
+path = \"../example.rs\"
+";
+        let package_dir = dir.path().join("package");
+        let manifest = absolute_dependency_paths(manifest, &package_dir);
+        let shared = dir.path().canonicalize().unwrap().join("shared");
+        let other = dir.path().canonicalize().unwrap().join("other");
+        assert_eq!(
+            super::to_f64_path_dependencies(&manifest),
+            format!(
+                "\
+[dependencies]
+shared = {{ package = \"shared-f64\", path = \"{}-f64\" }}
+other = {{ path = \"{}\", features = [\"a\"] }}
+
+[[example]]
+name = \"example\"
+path = \"../example.rs\"
+",
+                shared.display(),
+                other.display()
+            )
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! Parsing of the regions that markers delimit in the examples.
+
+use regex::Regex;
+use std::fmt;
+
+/// A region of an example, delimited by `// DOCUSAURUS: <name> start` and
+/// `// DOCUSAURUS: <name> stop` comments on their own lines, using the line comment syntax of
+/// the language of the example.
+///
+/// Regions can be nested, but not overlap.
+#[derive(Debug)]
+pub struct Region {
+    /// The name of the marker.
+    pub name: String,
+    /// Index of the line of the `start` comment, starting at 0.
+    pub start: usize,
+    /// Index of the line of the `stop` comment, starting at 0.
+    pub stop: usize,
+}
+
+/// An error in the markers of an example.
+#[derive(Debug, PartialEq)]
+pub enum RegionError {
+    /// A region stops inside a region it was started outside of.
+    Overlapping {
+        /// The region stopping.
+        marker: String,
+        /// The region still open when `marker` stops.
+        other: String,
+        /// Line of the `stop` comment, starting at 1.
+        line: usize,
+    },
+    /// A `stop` comment without a matching `start`.
+    NotStarted {
+        /// The region stopping.
+        marker: String,
+        /// Line of the `stop` comment, starting at 1.
+        line: usize,
+    },
+    /// A `start` comment without a matching `stop`.
+    NotStopped {
+        /// The region starting.
+        marker: String,
+        /// Line of the `start` comment, starting at 1.
+        line: usize,
+    },
+}
+
+impl RegionError {
+    /// The line where the error was found, starting at 1.
+    pub fn line(&self) -> usize {
+        match self {
+            RegionError::Overlapping { line, .. }
+            | RegionError::NotStarted { line, .. }
+            | RegionError::NotStopped { line, .. } => *line,
+        }
+    }
+
+    /// The marker causing the error.
+    pub fn marker(&self) -> &str {
+        match self {
+            RegionError::Overlapping { marker, .. }
+            | RegionError::NotStarted { marker, .. }
+            | RegionError::NotStopped { marker, .. } => marker,
+        }
+    }
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegionError::Overlapping {
+                marker,
+                other,
+                line,
+            } => write!(
+                f,
+                "marker {marker} stops at line {line} inside marker {other}, which started after it: regions can be nested but not overlap"
+            ),
+            RegionError::NotStarted { marker, line } => {
+                write!(f, "marker {marker} stops at line {line} but was never started")
+            }
+            RegionError::NotStopped { marker, line } => {
+                write!(f, "marker {marker} starts at line {line} but is never stopped")
+            }
+        }
+    }
+}
+
+/// A `start` or `stop` marker comment.
+struct MarkerLine<'a> {
+    name: &'a str,
+    start: bool,
+}
+
+/// The marker comments of examples written with a given line comment syntax.
+pub struct MarkerSyntax {
+    /// A `start` or `stop` marker comment, alone on its line.
+    marker_line: Regex,
+    /// The trailing comment of lines that are not injected.
+    hide_comment: String,
+}
+
+impl MarkerSyntax {
+    /// The marker comments written with the line `comment` syntax, e.g., `//` or `#`.
+    pub fn new(comment: &str) -> Self {
+        let escaped_comment = regex::escape(comment);
+        MarkerSyntax {
+            marker_line: Regex::new(&format!(
+                r"^\s*{escaped_comment} DOCUSAURUS: (\S+) (start|stop)\s*$"
+            ))
+            .unwrap(),
+            hide_comment: format!("{comment} DOCUSAURUS: hide"),
+        }
+    }
+
+    /// Parses `line` as a marker comment, if it is one.
+    fn parse_marker_line<'a>(&self, line: &'a str) -> Option<MarkerLine<'a>> {
+        let (_, [name, kind]) = self.marker_line.captures(line)?.extract();
+        Some(MarkerLine {
+            name,
+            start: kind == "start",
+        })
+    }
+
+    /// Whether `line` is a `start` or `stop` marker comment.
+    pub fn is_marker_line(&self, line: &str) -> bool {
+        self.parse_marker_line(line).is_some()
+    }
+
+    /// Whether `line` ends with a `DOCUSAURUS: hide` comment.
+    pub fn is_hidden_line(&self, line: &str) -> bool {
+        line.trim_end().ends_with(&self.hide_comment)
+    }
+
+    /// Finds every region of `source`, sorted by start line.
+    pub fn parse_regions(&self, source: &str) -> Result<Vec<Region>, RegionError> {
+        let mut regions = Vec::new();
+        // Regions started but not stopped yet, innermost last.
+        let mut open: Vec<(&str, usize)> = Vec::new();
+
+        for (i, line) in source.lines().enumerate() {
+            let Some(marker) = self.parse_marker_line(line) else {
+                continue;
+            };
+            if marker.start {
+                open.push((marker.name, i));
+                continue;
+            }
+            match open.iter().rposition(|(name, _)| *name == marker.name) {
+                Some(index) if index + 1 == open.len() => {
+                    let (name, start) = open.pop().unwrap();
+                    regions.push(Region {
+                        name: name.to_string(),
+                        start,
+                        stop: i,
+                    });
+                }
+                Some(_) => {
+                    return Err(RegionError::Overlapping {
+                        marker: marker.name.to_string(),
+                        other: open.last().unwrap().0.to_string(),
+                        line: i + 1,
+                    })
+                }
+                None => {
+                    return Err(RegionError::NotStarted {
+                        marker: marker.name.to_string(),
+                        line: i + 1,
+                    })
+                }
+            }
+        }
+
+        if let Some((name, start)) = open.pop() {
+            return Err(RegionError::NotStopped {
+                marker: name.to_string(),
+                line: start + 1,
+            });
+        }
+        regions.sort_by_key(|region| region.start);
+        Ok(regions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Result<Vec<(String, usize, usize)>, RegionError> {
+        let regions = MarkerSyntax::new("//").parse_regions(source)?;
+        Ok(regions
+            .into_iter()
+            .map(|region| (region.name, region.start, region.stop))
+            .collect())
+    }
+
+    #[test]
+    fn nested_regions_are_sorted_by_start() {
+        let source = "\
+// DOCUSAURUS: outer start
+let a = 1;
+    // DOCUSAURUS: inner start
+    let b = 2;
+    // DOCUSAURUS: inner stop
+// DOCUSAURUS: outer stop
+";
+        assert_eq!(
+            parse(source),
+            Ok(vec![
+                ("outer".to_string(), 0, 5),
+                ("inner".to_string(), 2, 4)
+            ])
+        );
+    }
+
+    #[test]
+    fn regions_with_the_same_name_are_all_found() {
+        let source = "\
+// DOCUSAURUS: a start
+// DOCUSAURUS: a stop
+// DOCUSAURUS: a start
+// DOCUSAURUS: a stop
+";
+        assert_eq!(
+            parse(source),
+            Ok(vec![("a".to_string(), 0, 1), ("a".to_string(), 2, 3)])
+        );
+    }
+
+    #[test]
+    fn overlapping_regions_are_rejected() {
+        let source = "\
+// DOCUSAURUS: a start
+// DOCUSAURUS: b start
+// DOCUSAURUS: a stop
+// DOCUSAURUS: b stop
+";
+        let error = parse(source).unwrap_err();
+        assert_eq!(
+            error,
+            RegionError::Overlapping {
+                marker: "a".to_string(),
+                other: "b".to_string(),
+                line: 3,
+            }
+        );
+        assert_eq!(error.marker(), "a");
+        assert_eq!(error.line(), 3);
+    }
+
+    #[test]
+    fn unbalanced_markers_are_rejected() {
+        assert_eq!(
+            parse("let a = 1;\n// DOCUSAURUS: a stop\n"),
+            Err(RegionError::NotStarted {
+                marker: "a".to_string(),
+                line: 2,
+            })
+        );
+        assert_eq!(
+            parse("// DOCUSAURUS: a start\n// DOCUSAURUS: b start\n// DOCUSAURUS: b stop\n"),
+            Err(RegionError::NotStopped {
+                marker: "a".to_string(),
+                line: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn markers_use_the_comment_syntax_of_the_language() {
+        let syntax = MarkerSyntax::new("#");
+        let regions = syntax
+            .parse_regions("# DOCUSAURUS: a start\n// DOCUSAURUS: b start\n# DOCUSAURUS: a stop\n")
+            .unwrap();
+        assert_eq!(regions.len(), 1);
+        assert!(syntax.is_marker_line("    # DOCUSAURUS: a stop  "));
+        assert!(!syntax.is_marker_line("x = 1  # DOCUSAURUS: a stop"));
+        assert!(syntax.is_hidden_line("import math  # DOCUSAURUS: hide"));
+        assert!(!syntax.is_hidden_line("import math  // DOCUSAURUS: hide"));
+    }
+}
@@ -0,0 +1,303 @@
+//! The public API of the documented crates, read from the JSON output of rustdoc.
+//!
+//! The JSON output is unstable, and its format changes between nightly versions, so the
+//! crates are documented with the pinned [`NIGHTLY`] toolchain, and any other format is
+//! rejected rather than misread. Hidden items, like the `#[doc(hidden)]` modules parry re-exports its types from, are
+//! documented too, but not private ones.
+
+use crate::scratch::cargo;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    process::Command,
+};
+
+/// The nightly toolchain the crates are documented with.
+const NIGHTLY: &str = "nightly-2026-05-19";
+
+/// The version of the rustdoc JSON format written by [`NIGHTLY`], the only one this module
+/// reads.
+const FORMAT_VERSION: u64 = 57;
+
+/// The public items of some crates.
+#[derive(Default)]
+pub struct Api {
+    /// Names of the crates, with their version, e.g. `parry2d-0.17.3`.
+    pub crates: Vec<String>,
+    /// Names of every public item: modules, types, functions, methods, fields, enum
+    /// variants, constants and re-exports.
+    pub items: HashSet<String>,
+    /// Names of the modules.
+    pub modules: HashSet<String>,
+    /// Names of the associated items, fields and variants of each struct, enum, union and
+    /// trait.
+    pub members: HashMap<String, HashSet<String>>,
+}
+
+impl Api {
+    /// Whether `name` is a struct, enum, union or trait.
+    pub fn is_type(&self, name: &str) -> bool {
+        self.members.contains_key(name)
+    }
+
+    /// Whether `member` is an associated item, field or variant of the type `name`.
+    pub fn is_member(&self, name: &str, member: &str) -> bool {
+        self.members
+            .get(name)
+            .is_some_and(|members| members.contains(member))
+    }
+
+    /// Adds the public items of the rustdoc JSON `doc`.
+    fn extend(&mut self, doc: &Value) {
+        let empty = serde_json::Map::new();
+        let index = doc["index"].as_object().unwrap_or(&empty);
+        let item = |id: &Value| index.get(&id.to_string()).filter(|item| is_public(item));
+        let names = |ids: &Value| {
+            ids.as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(item)
+                .filter_map(item_name)
+                .collect::<Vec<_>>()
+        };
+
+        for item in index
+            .values()
+            .filter(|item| is_local(item) && is_public(item))
+        {
+            let Some(name) = item_name(item) else {
+                continue;
+            };
+            self.items.insert(name.to_string());
+            let Some((kind, inner)) = item["inner"]
+                .as_object()
+                .and_then(|inner| inner.iter().next())
+            else {
+                continue;
+            };
+            let members = match kind.as_str() {
+                "module" => {
+                    self.modules.insert(name.to_string());
+                    continue;
+                }
+                "struct" => [
+                    names(&inner["kind"]["plain"]["fields"]),
+                    impl_members(&inner["impls"], index),
+                ]
+                .concat(),
+                "union" => [
+                    names(&inner["fields"]),
+                    impl_members(&inner["impls"], index),
+                ]
+                .concat(),
+                "enum" => [
+                    names(&inner["variants"]),
+                    impl_members(&inner["impls"], index),
+                ]
+                .concat(),
+                "trait" => names(&inner["items"]),
+                _ => continue,
+            };
+            self.members
+                .entry(name.to_string())
+                .or_default()
+                .extend(members.into_iter().map(str::to_string));
+        }
+    }
+}
+
+/// Whether `item` belongs to the documented crate rather than to one of its dependencies.
+fn is_local(item: &Value) -> bool {
+    item["crate_id"] == 0
+}
+
+/// Whether `item` is public, or has the visibility of its parent, like enum variants and the
+/// items of trait implementations.
+fn is_public(item: &Value) -> bool {
+    item["visibility"] == "public" || item["visibility"] == "default"
+}
+
+/// The name of `item`, or the name it is re-exported as, like `Real` for
+/// `pub use f32 as Real`.
+///
+/// Implementations and glob re-exports have no name.
+fn item_name(item: &Value) -> Option<&str> {
+    let reexport = &item["inner"]["use"];
+    if reexport.is_object() {
+        if reexport["is_glob"] == true {
+            return None;
+        }
+        return reexport["name"].as_str();
+    }
+    item["name"].as_str()
+}
+
+/// The names of the items of the implementations `impls`, including the provided methods of
+/// the implemented traits.
+fn impl_members<'a>(impls: &Value, index: &'a serde_json::Map<String, Value>) -> Vec<&'a str> {
+    let mut members = Vec::new();
+    for implementation in impls.as_array().into_iter().flatten() {
+        let Some(implementation) = index.get(&implementation.to_string()) else {
+            continue;
+        };
+        let implementation = &implementation["inner"]["impl"];
+        let items = implementation["items"].as_array().into_iter().flatten();
+        members.extend(
+            items
+                .filter_map(|id| index.get(&id.to_string()))
+                .filter(|item| is_public(item))
+                .filter_map(item_name),
+        );
+        let provided = implementation["provided_trait_methods"].as_array();
+        members.extend(provided.into_iter().flatten().filter_map(Value::as_str));
+    }
+    members
+}
+
+/// Checks that `doc` has the [`FORMAT_VERSION`] this module reads.
+fn check_format(doc: &Value) -> Result<(), String> {
+    match doc["format_version"].as_u64() {
+        Some(FORMAT_VERSION) => Ok(()),
+        Some(version) => Err(format!(
+            "the rustdoc JSON format {version} is not supported, only {FORMAT_VERSION}, \
+            written by {NIGHTLY}"
+        )),
+        None => Err("the rustdoc JSON has no format version".to_string()),
+    }
+}
+
+/// The name and version of each of the `crates` the workspace of `examples_dir` depends on.
+fn locked_packages(
+    examples_dir: &Path,
+    crates: &[String],
+) -> Result<Vec<(String, String)>, String> {
+    let output = cargo(examples_dir, &["metadata", "--format-version", "1"])?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    let metadata: Value =
+        serde_json::from_slice(&output.stdout).map_err(|error| error.to_string())?;
+    let packages = metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|package| {
+            crates
+                .iter()
+                .any(|name| package["name"].as_str() == Some(name.as_str()))
+        })
+        .filter_map(|package| {
+            Some((
+                package["name"].as_str()?.to_string(),
+                package["version"].as_str()?.to_string(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    if packages.is_empty() {
+        return Err("the examples depend on none of them".to_string());
+    }
+    Ok(packages)
+}
+
+/// The public API of the `crates` the workspace of `examples_dir` depends on, at their locked
+/// versions.
+///
+/// The crates are documented in a temporary target directory, kept between runs so that they
+/// are only documented once.
+pub fn crate_api(examples_dir: &Path, crates: &[String]) -> Result<Api, String> {
+    let target_dir = std::env::temp_dir().join("inject_file").join("rustdoc");
+    let mut api = Api::default();
+    for (name, version) in locked_packages(examples_dir, crates)? {
+        let package = format!("{name}@{version}");
+        let output = Command::new("cargo")
+            .arg(format!("+{NIGHTLY}"))
+            .args(["doc", "--no-deps", "--package", &package])
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .env(
+                "RUSTDOCFLAGS",
+                "-Z unstable-options --output-format json --document-hidden-items",
+            )
+            .current_dir(examples_dir)
+            .output()
+            .map_err(|error| format!("could not run cargo +{NIGHTLY}: {error}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "could not document {package} with the {NIGHTLY} toolchain: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        let json_path = target_dir
+            .join("doc")
+            .join(format!("{}.json", name.replace('-', "_")));
+        let doc = fs::read_to_string(&json_path)
+            .map_err(|error| format!("could not read {}: {error}", json_path.display()))?;
+        let doc: Value = serde_json::from_str(&doc)
+            .map_err(|error| format!("could not parse {}: {error}", json_path.display()))?;
+        check_format(&doc).map_err(|error| format!("{}: {error}", json_path.display()))?;
+        api.extend(&doc);
+        api.crates.push(format!("{name}-{version}"));
+    }
+    Ok(api)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn public_items_and_members_are_read() {
+        let doc = serde_json::json!({
+            "index": {
+                "1": {"crate_id": 0, "name": "shape", "visibility": "public",
+                      "inner": {"module": {"items": [2, 6]}}},
+                "2": {"crate_id": 0, "name": "Aabb", "visibility": "public",
+                      "inner": {"struct": {"kind": {"plain": {"fields": [3, 4]}},
+                                           "impls": [5, 9]}}},
+                "3": {"crate_id": 0, "name": "mins", "visibility": "public",
+                      "inner": {"struct_field": {}}},
+                "4": {"crate_id": 0, "name": "cache", "visibility": "crate",
+                      "inner": {"struct_field": {}}},
+                "5": {"crate_id": 0, "name": null, "visibility": "default",
+                      "inner": {"impl": {"items": [7, 8], "provided_trait_methods": []}}},
+                "6": {"crate_id": 0, "name": null, "visibility": "public",
+                      "inner": {"use": {"source": "f32", "name": "Real", "id": null,
+                                        "is_glob": false}}},
+                "7": {"crate_id": 0, "name": "new_invalid", "visibility": "public",
+                      "inner": {"function": {}}},
+                "8": {"crate_id": 0, "name": "merge_cache", "visibility": "crate",
+                      "inner": {"function": {}}},
+                "9": {"crate_id": 0, "name": null, "visibility": "default",
+                      "inner": {"impl": {"items": [], "provided_trait_methods": ["merged"]}}},
+                "10": {"crate_id": 1, "name": "Vector3", "visibility": "public",
+                       "inner": {"type_alias": {}}}
+            }
+        });
+        let mut api = Api::default();
+        api.extend(&doc);
+
+        let mut items = api.items.iter().map(String::as_str).collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(items, ["Aabb", "Real", "mins", "new_invalid", "shape"]);
+        assert!(api.modules.contains("shape"));
+        assert!(api.is_type("Aabb"));
+        assert!(!api.is_type("Real"));
+        assert!(api.is_member("Aabb", "mins"));
+        assert!(api.is_member("Aabb", "new_invalid"));
+        assert!(api.is_member("Aabb", "merged"));
+        assert!(!api.is_member("Aabb", "cache"));
+        assert!(!api.is_member("Aabb", "merge_cache"));
+    }
+
+    #[test]
+    fn only_the_pinned_format_is_read() {
+        assert_eq!(
+            check_format(&serde_json::json!({"format_version": FORMAT_VERSION})),
+            Ok(())
+        );
+        assert!(check_format(&serde_json::json!({"format_version": FORMAT_VERSION + 1})).is_err());
+        assert!(check_format(&serde_json::json!({"index": {}})).is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Temporary cargo packages where generated code is compiled.
+
+use regex::{Captures, Regex};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// Creates the temporary cargo package `name`, with the given `manifest` and no examples.
+///
+/// The package is kept between runs so its dependencies are only built once.
+pub fn scratch_package(name: &str, manifest: &str) -> Result<PathBuf, String> {
+    let dir = std::env::temp_dir().join("inject_file").join(name);
+    let examples_dir = dir.join("examples");
+    if examples_dir.exists() {
+        fs::remove_dir_all(&examples_dir)
+            .map_err(|error| format!("could not clear {}: {error}", examples_dir.display()))?;
+    }
+    // The empty workspace keeps the package out of any workspace above the temporary directory.
+    write_file(
+        &dir.join("Cargo.toml"),
+        &format!("{manifest}\n[workspace]\n"),
+    )?;
+    write_file(&dir.join("src/main.rs"), "fn main() {}\n")?;
+    Ok(dir)
+}
+
+/// Rewrites the relative paths of the dependencies of `manifest`, the `Cargo.toml` of the
+/// package at `package_dir`, to absolute paths, so that they resolve from a scratch package.
+pub fn absolute_dependency_paths(manifest: &str, package_dir: &Path) -> String {
+    // Dependencies are inline tables, unlike the `path` of targets like `[[example]]`.
+    let re_path = Regex::new(r#"(\{[^}\n]*\bpath\s*=\s*")([^"]+)(")"#).unwrap();
+    re_path
+        .replace_all(manifest, |dependency: &Captures| {
+            let path = package_dir.join(&dependency[2]);
+            let path = fs::canonicalize(&path).unwrap_or(path);
+            format!("{}{}{}", &dependency[1], path.display(), &dependency[3])
+        })
+        .into_owned()
+}
+
+/// Writes `content` to `path`, creating its parent directories.
+pub fn write_file(path: &Path, content: &str) -> Result<(), String> {
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|_| fs::write(path, content))
+        .map_err(|error| format!("could not write {}: {error}", path.display()))
+}
+
+/// Runs cargo with `args` in the package at `dir`.
+pub fn cargo(dir: &Path, args: &[&str]) -> Result<Output, String> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    Command::new(cargo)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|error| format!("could not run cargo: {error}"))
+}
+
+/// The package of the cargo example at `example`, i.e. the closest directory above it with a
+/// `Cargo.toml`.
+pub fn example_package(example: &Path) -> Result<&Path, String> {
+    example
+        .ancestors()
+        .skip(1)
+        .find(|dir| dir.join("Cargo.toml").is_file())
+        .ok_or_else(|| "no Cargo.toml found above it".to_string())
+}
+
+/// Copies the `Cargo.lock` of the workspace of the package at `package_dir` to the scratch
+/// package at `dir`, so that it builds with the same versions of its dependencies.
+pub fn copy_lockfile(package_dir: &Path, dir: &Path) -> Result<(), String> {
+    let output = cargo(
+        package_dir,
+        &["locate-project", "--workspace", "--message-format", "plain"],
+    )?;
+    if !output.status.success() {
+        return Err(format!(
+            "could not find its workspace:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim_end()
+        ));
+    }
+    let workspace_manifest = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim_end());
+    let lockfile = workspace_manifest.with_file_name("Cargo.lock");
+    if !lockfile.is_file() {
+        return Ok(());
+    }
+    fs::copy(&lockfile, dir.join("Cargo.lock"))
+        .map(|_| ())
+        .map_err(|error| format!("could not copy {}: {error}", lockfile.display()))
+}
@@ -0,0 +1,257 @@
+//! Parsing of the `<load>` and `<load-dims>` tags of templates.
+
+use crate::Config;
+use regex::Regex;
+use std::ops::{Range, RangeInclusive};
+
+/// The dimensions of the examples loaded by a `<load-dims>` tag.
+pub const DIMS: [u8; 2] = [2, 3];
+
+/// A well-formed tag found in a template.
+pub enum Tag {
+    /// A `<load>` tag.
+    Load(LoadTag),
+    /// A `<load-dims>` tag.
+    Dims(DimsTag),
+}
+
+/// A well-formed `<load>` tag found in a template.
+#[derive(Clone)]
+pub struct LoadTag {
+    /// The path of the loaded file, as written in the tag.
+    pub path: String,
+    /// The name of the loaded marker.
+    pub marker: String,
+    /// Whether the common indentation of the region is removed, unless `dedent='false'`.
+    pub dedent: bool,
+    /// The lines of the region to inject, starting at 1, from `lines='a-b'` or `lines='a'`.
+    ///
+    /// The range is only checked against the region when injecting it.
+    pub lines: Option<RangeInclusive<usize>>,
+    /// Whether the output the example prints is injected instead of its code, if
+    /// `output='true'`.
+    pub output: bool,
+    /// Whether the `f64` variant of the code is injected, if `f64='true'`.
+    pub f64: bool,
+    /// Byte range of the tag in the template, including its trailing newline if any.
+    pub range: Range<usize>,
+}
+
+/// A well-formed `<load-dims>` tag, loading the same marker from the 2D and 3D examples
+/// named `name`, e.g. `/<dim>d/rust/examples/<name><dim>.rs`.
+pub struct DimsTag {
+    /// The name of the examples, without their dimension.
+    pub name: String,
+    /// The name of the loaded marker.
+    pub marker: String,
+    /// Whether each dimension has an `f32` and an `f64` tab, if `f64='true'`.
+    pub f64: bool,
+    /// The loaded dimensions, all the [`DIMS`] unless restricted with e.g. `dims='3'`.
+    pub dims: Vec<u8>,
+    /// Byte range of the tag in the template, including its trailing newline if any.
+    pub range: Range<usize>,
+}
+
+impl DimsTag {
+    /// The `<load>` tag of the example of each of the `self.dims`, loading its `f64` variant
+    /// if `self.f64` is set.
+    ///
+    /// The path of each example is given by [`Config::dims_example`].
+    pub fn load_tags(&self, config: &Config) -> Vec<(u8, LoadTag)> {
+        self.dims
+            .iter()
+            .map(|&dim| {
+                let tag = LoadTag {
+                    path: config.dims_example(dim, &self.name),
+                    marker: self.marker.clone(),
+                    dedent: true,
+                    lines: None,
+                    output: false,
+                    f64: self.f64,
+                    range: self.range.clone(),
+                };
+                (dim, tag)
+            })
+            .collect()
+    }
+}
+
+/// Finds every `<load>` and `<load-dims>` tag in `template`.
+///
+/// Tags are written `<load path='...' marker='...' />` or
+/// `<load-dims name='...' marker='...' />`, with attributes delimited by `'`. A malformed tag,
+/// or one with a missing or unknown attribute, is returned as the byte offset where it starts.
+pub fn find_tags(template: &str) -> Vec<Result<Tag, usize>> {
+    let re_start = Regex::new(r"<load(-dims)?\s").unwrap();
+    let re_tag = Regex::new(r"^<(load|load-dims)((?:\s+[\w-]+='[^']*')*)\s*/?>\n?").unwrap();
+    let re_attribute = Regex::new(r"([\w-]+)='([^']*)'").unwrap();
+
+    re_start
+        .find_iter(template)
+        .map(|start| {
+            let start = start.start();
+            let caps = re_tag.captures(&template[start..]).ok_or(start)?;
+            let range = start..start + caps[0].len();
+            let attributes = re_attribute
+                .captures_iter(&caps[2])
+                .map(|attribute| attribute.extract().1);
+            if &caps[1] == "load-dims" {
+                parse_dims_tag(attributes, range).map(Tag::Dims)
+            } else {
+                parse_load_tag(attributes, range).map(Tag::Load)
+            }
+            .ok_or(start)
+        })
+        .collect()
+}
+
+/// Parses the attributes of a `<load>` tag.
+fn parse_load_tag<'a>(
+    attributes: impl Iterator<Item = [&'a str; 2]>,
+    range: Range<usize>,
+) -> Option<LoadTag> {
+    let mut path = None;
+    let mut marker = None;
+    let mut dedent = true;
+    let mut lines = None;
+    let mut output = false;
+    let mut f64 = false;
+    for [name, value] in attributes {
+        match (name, value) {
+            ("path", _) => path = Some(value.to_string()),
+            ("marker", _) => marker = Some(value.to_string()),
+            ("dedent", "true") => dedent = true,
+            ("dedent", "false") => dedent = false,
+            ("output", "true") => output = true,
+            ("output", "false") => output = false,
+            ("f64", "true") => f64 = true,
+            ("f64", "false") => f64 = false,
+            ("lines", _) => lines = Some(parse_lines(value)?),
+            _ => return None,
+        }
+    }
+    Some(LoadTag {
+        path: path?,
+        marker: marker?,
+        dedent,
+        lines,
+        output,
+        f64,
+        range,
+    })
+}
+
+/// Parses the attributes of a `<load-dims>` tag.
+fn parse_dims_tag<'a>(
+    attributes: impl Iterator<Item = [&'a str; 2]>,
+    range: Range<usize>,
+) -> Option<DimsTag> {
+    let mut name = None;
+    let mut marker = None;
+    let mut f64 = false;
+    let mut dims = DIMS.to_vec();
+    for [attribute, value] in attributes {
+        match (attribute, value) {
+            ("name", _) => name = Some(value.to_string()),
+            ("marker", _) => marker = Some(value.to_string()),
+            ("f64", "true") => f64 = true,
+            ("f64", "false") => f64 = false,
+            ("dims", _) => dims = parse_dims(value)?,
+            _ => return None,
+        }
+    }
+    Some(DimsTag {
+        name: name?,
+        marker: marker?,
+        f64,
+        dims,
+        range,
+    })
+}
+
+/// Parses a `dims` attribute, a comma-separated list of some of the [`DIMS`], e.g. `3` or
+/// `2,3`.
+fn parse_dims(value: &str) -> Option<Vec<u8>> {
+    let dims = value
+        .split(',')
+        .map(|dim| dim.trim().parse().ok().filter(|dim| DIMS.contains(dim)))
+        .collect::<Option<Vec<u8>>>()?;
+    (!dims.is_empty()).then_some(dims)
+}
+
+/// Parses a `lines` attribute, either `a-b` or `a`.
+fn parse_lines(value: &str) -> Option<RangeInclusive<usize>> {
+    let (first, last) = value.split_once('-').unwrap_or((value, value));
+    let first = first.trim().parse().ok()?;
+    let last = last.trim().parse().ok()?;
+    Some(first..=last)
+}
+
+/// Finds every `<load>` tag in `template`, `<load-dims>` tags standing for the `<load>` tags
+/// of each of their dimensions.
+///
+/// Malformed tags are skipped here and reported by the injection itself.
+pub fn load_tags(template: &str, config: &Config) -> Vec<LoadTag> {
+    find_tags(template)
+        .into_iter()
+        .flatten()
+        .flat_map(|tag| match tag {
+            Tag::Load(tag) => vec![tag],
+            Tag::Dims(tag) => tag
+                .load_tags(config)
+                .into_iter()
+                .map(|(_, tag)| tag)
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_tag(template: &str) -> Option<LoadTag> {
+        match find_tags(template).pop()? {
+            Ok(Tag::Load(tag)) => Some(tag),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn lines_are_parsed_without_validation() {
+        let tag = |lines: &str| {
+            load_tag(&format!("<load path='a.rs' marker='m' lines='{lines}' />"))
+                .map(|tag| tag.lines)
+        };
+        assert_eq!(tag("2-4"), Some(Some(2..=4)));
+        assert_eq!(tag("3"), Some(Some(3..=3)));
+        // Checked against the region when injecting it.
+        assert_eq!(tag("0-2"), Some(Some(0..=2)));
+        assert_eq!(tag("4-2"), Some(Some(RangeInclusive::new(4, 2))));
+        assert_eq!(tag("a-b"), None);
+    }
+
+    #[test]
+    fn malformed_tags_are_reported_at_their_start() {
+        let template = "text\n<load path='a.rs' />\n<load path='a.rs' marker='m' color='red' />\n";
+        let tags = find_tags(template);
+        assert!(matches!(tags[..], [Err(5), Err(26)]));
+    }
+
+    #[test]
+    fn dims_tags_load_each_dimension() {
+        let template = "<load-dims name='shapes' marker='m' f64='true' dims='3' />\n";
+        let tags = load_tags(template, &Config::default());
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].path, "/3d/rust/examples/shapes3.rs");
+        assert!(tags[0].f64);
+        assert_eq!(tags[0].range, 0..template.len());
+
+        let tags = load_tags("<load-dims name='shapes' marker='m' />", &Config::default());
+        assert_eq!(tags.len(), 2);
+        for dims in ["1", "", "2,4"] {
+            let template = format!("<load-dims name='shapes' marker='m' dims='{dims}' />");
+            assert!(matches!(find_tags(&template)[..], [Err(0)]), "{dims}");
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Helpers of the unit tests.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A fresh temporary directory, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Creates the empty directory of the test `name`.
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir()
+            .join("inject_file_tests")
+            .join(format!("{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+
+    /// The path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+
+    /// Writes `content` to the file at `path` in the directory, creating its parents.
+    pub fn write(&self, path: &str, content: &str) -> PathBuf {
+        let path = self.0.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
@@ -0,0 +1,341 @@
+//! Template variables, like `{{parry3d_version}}`, filled from the manifests of the examples.
+
+use crate::diagnostics::line_column;
+use crate::inject::{ErrorType, InjectError};
+use regex::Regex;
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The values of the variables of the templates, by name.
+///
+/// For every dependency of the `*/rust/Cargo.toml` example packages of a root, e.g. `parry3d`,
+/// `{{parry3d_version}}` is the version locked by the committed `Cargo.lock` of the root, and
+/// `{{parry3d_features}}` the quoted features it enables, e.g. `"rand"`. A renamed dependency
+/// is named after its package, e.g. `{{parry2d_f64_version}}`. `{{parry_version}}` is the
+/// version shared by `parry2d` and `parry3d`.
+#[derive(Debug, Default)]
+pub struct Variables {
+    values: BTreeMap<String, String>,
+    sources: Vec<PathBuf>,
+}
+
+impl Variables {
+    /// Reads the variables of the example packages under `root`.
+    ///
+    /// The error tells why a version could not be resolved: the `Cargo.lock` of `root` is
+    /// missing, or locks no version matching the requirement of a manifest.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let mut variables = Variables::default();
+        let lock_path = root.join("Cargo.lock");
+        let lock = fs::read_to_string(&lock_path).map_err(|error| {
+            format!(
+                "could not read {}, which locks the versions of the crates: {error}",
+                lock_path.display()
+            )
+        })?;
+        let locked = locked_versions(&lock);
+        variables.sources.push(lock_path.clone());
+
+        let mut dirs = fs::read_dir(root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        dirs.sort();
+        for dir in dirs {
+            let manifest_path = dir.join("rust/Cargo.toml");
+            let Ok(manifest) = fs::read_to_string(&manifest_path) else {
+                continue;
+            };
+            for (name, requirement, features) in dependencies(&manifest) {
+                let variable = name.replace('-', "_");
+                let version = locked
+                    .get(&name)
+                    .and_then(|versions| {
+                        versions
+                            .iter()
+                            .find(|version| matches_requirement(version, &requirement))
+                    })
+                    .ok_or_else(|| {
+                        format!(
+                            "{} locks no version of {name} matching {requirement}, required by {}, run `cargo update` to update it",
+                            lock_path.display(),
+                            manifest_path.display()
+                        )
+                    })?;
+                variables
+                    .values
+                    .entry(format!("{variable}_version"))
+                    .or_insert(version.clone());
+                variables
+                    .values
+                    .entry(format!("{variable}_features"))
+                    .or_insert(features);
+            }
+            variables.sources.push(manifest_path);
+        }
+
+        let mut parry_versions = variables
+            .values
+            .iter()
+            .filter(|(name, _)| ["parry2d_version", "parry3d_version"].contains(&name.as_str()))
+            .map(|(_, version)| version.clone())
+            .collect::<Vec<_>>();
+        parry_versions.sort();
+        parry_versions.dedup();
+        if let [version] = &parry_versions[..] {
+            variables
+                .values
+                .insert("parry_version".to_string(), version.clone());
+        }
+        Ok(variables)
+    }
+
+    /// The value of the variable `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// The name and value of every variable, sorted by name.
+    pub fn values(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// The files the values were read from.
+    pub fn sources(&self) -> &[PathBuf] {
+        &self.sources
+    }
+
+    /// Replaces every `{{name}}` placeholder of `template` by the value of its variable.
+    ///
+    /// Unknown variables are reported at their placeholder, and left as they are.
+    pub fn substitute(&self, template: &str) -> (String, Vec<InjectError>) {
+        let mut errors = Vec::new();
+        let result = placeholder_regex().replace_all(template, |placeholder: &regex::Captures| {
+            let name = &placeholder[1];
+            match self.get(name) {
+                Some(value) => value.to_string(),
+                None => {
+                    let (line, column) = line_column(template, placeholder.get(0).unwrap().start());
+                    errors.push(InjectError {
+                        line,
+                        column,
+                        error: ErrorType::UnknownVariable(name.to_string()),
+                    });
+                    placeholder[0].to_string()
+                }
+            }
+        });
+        (result.into_owned(), errors)
+    }
+}
+
+/// Whether `template` contains a `{{name}}` placeholder.
+pub fn has_placeholders(template: &str) -> bool {
+    placeholder_regex().is_match(template)
+}
+
+/// Matches `{{name}}` placeholders, but not the `{{key: value}}` objects of JSX attributes.
+fn placeholder_regex() -> Regex {
+    Regex::new(r"\{\{\s*([a-z][a-z0-9_]*)\s*\}\}").unwrap()
+}
+
+/// The locked versions of each package of a `Cargo.lock`.
+fn locked_versions(lock: &str) -> BTreeMap<String, Vec<String>> {
+    let re_package = Regex::new(r#"(?m)^name = "([^"]+)"\r?\nversion = "([^"]+)""#).unwrap();
+    let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for package in re_package.captures_iter(lock) {
+        versions
+            .entry(package[1].to_string())
+            .or_default()
+            .push(package[2].to_string());
+    }
+    versions
+}
+
+/// The package name, version requirement and quoted features of the `[dependencies]` of
+/// `manifest`.
+fn dependencies(manifest: &str) -> Vec<(String, String, String)> {
+    let re_dependency = Regex::new(r#"^([\w-]+)\s*=\s*(?:"([^"]*)"|\{(.*)\})"#).unwrap();
+    let re_version = Regex::new(r#"\bversion\s*=\s*"([^"]*)""#).unwrap();
+    let re_features = Regex::new(r"\bfeatures\s*=\s*\[([^\]]*)\]").unwrap();
+    let re_package = Regex::new(r#"\bpackage\s*=\s*"([^"]*)""#).unwrap();
+    let mut dependencies = Vec::new();
+    let mut in_dependencies = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_dependencies = line == "[dependencies]";
+            continue;
+        }
+        let Some(dependency) = re_dependency.captures(line).filter(|_| in_dependencies) else {
+            continue;
+        };
+        let package = dependency
+            .get(3)
+            .and_then(|table| re_package.captures(table.as_str()))
+            .map_or(dependency[1].to_string(), |package| package[1].to_string());
+        let (requirement, features) = match (dependency.get(2), dependency.get(3)) {
+            (Some(requirement), _) => (requirement.as_str().to_string(), String::new()),
+            (_, Some(table)) => (
+                re_version
+                    .captures(table.as_str())
+                    .map(|version| version[1].to_string())
+                    .unwrap_or_else(|| "*".to_string()),
+                re_features
+                    .captures(table.as_str())
+                    .map(|features| features[1].trim().to_string())
+                    .unwrap_or_default(),
+            ),
+            _ => continue,
+        };
+        dependencies.push((package, requirement, features));
+    }
+    dependencies
+}
+
+/// Whether the locked `version` may have been resolved from `requirement`, e.g. `0.17.3`
+/// from `0.17` or `^0.17.0`.
+fn matches_requirement(version: &str, requirement: &str) -> bool {
+    let requirement = requirement.trim_start_matches(['^', '=', '~', ' ']);
+    if requirement == "*" {
+        return true;
+    }
+    // Cargo resolves `0.17.0` to the latest `0.17.*`, and `1.2` to the latest `1.*`.
+    let compatible = requirement
+        .split('.')
+        .scan(true, |leading_zeros, part| {
+            let keep = *leading_zeros;
+            *leading_zeros &= part == "0";
+            keep.then_some(part)
+        })
+        .collect::<Vec<_>>();
+    version
+        .split('.')
+        .zip(&compatible)
+        .all(|(part, required)| part == *required)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requirements_match_compatible_versions() {
+        assert!(matches_requirement("0.17.3", "0.17"));
+        assert!(matches_requirement("0.17.3", "^0.17.0"));
+        assert!(matches_requirement("0.17.3", "=0.17.3"));
+        assert!(matches_requirement("1.5.0", "1.2"));
+        assert!(matches_requirement("0.0.3", "0.0.3"));
+        assert!(matches_requirement("2.0.0", "*"));
+        assert!(!matches_requirement("0.18.0", "0.17"));
+        assert!(!matches_requirement("2.0.0", "1.2"));
+        assert!(!matches_requirement("0.0.4", "0.0.3"));
+    }
+
+    #[test]
+    fn lockfiles_list_every_version() {
+        let lock = "\
+version = 4
+
+[[package]]
+name = \"nalgebra\"
+version = \"0.33.2\"
+source = \"registry+https://github.com/rust-lang/crates.io-index\"
+
+[[package]]
+name = \"nalgebra\"
+version = \"0.32.6\"
+
+[[package]]
+name = \"parry2d\"
+version = \"0.17.1\"
+dependencies = [
+ \"nalgebra 0.33.2\",
+]
+";
+        let versions = locked_versions(lock);
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions["nalgebra"], ["0.33.2", "0.32.6"]);
+        assert_eq!(versions["parry2d"], ["0.17.1"]);
+    }
+
+    #[test]
+    fn dependencies_are_read_from_their_section() {
+        let manifest = r#"
+[package]
+name = "examples"
+version = "0.1.0"
+
+[dependencies]
+parry2d = "0.17"
+na = { package = "nalgebra", version = "0.33", features = ["serde-serialize"] }
+rand = { git = "https://github.com/rust-random/rand" }
+
+[dev-dependencies]
+approx = "0.5"
+"#;
+        let dependency = |package: &str, requirement: &str, features: &str| {
+            (
+                package.to_string(),
+                requirement.to_string(),
+                features.to_string(),
+            )
+        };
+        assert_eq!(
+            dependencies(manifest),
+            vec![
+                dependency("parry2d", "0.17", ""),
+                dependency("nalgebra", "0.33", "\"serde-serialize\""),
+                dependency("rand", "*", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn variables_are_resolved_from_the_lock() {
+        let root =
+            std::env::temp_dir().join(format!("inject_file_variables_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("2d/rust")).unwrap();
+        fs::write(
+            root.join("2d/rust/Cargo.toml"),
+            "[dependencies]\nparry2d = { version = \"0.17\", features = [\"simd-stable\"] }\n",
+        )
+        .unwrap();
+        let missing_lock = Variables::load(&root).unwrap_err();
+
+        fs::write(
+            root.join("Cargo.lock"),
+            "[[package]]\nname = \"parry2d\"\nversion = \"0.16.0\"\n",
+        )
+        .unwrap();
+        let outdated_lock = Variables::load(&root).unwrap_err();
+
+        fs::write(
+            root.join("Cargo.lock"),
+            "[[package]]\nname = \"parry2d\"\nversion = \"0.17.1\"\n",
+        )
+        .unwrap();
+        let variables = Variables::load(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(missing_lock.contains("could not read"), "{missing_lock}");
+        assert!(
+            outdated_lock.contains("locks no version of parry2d matching 0.17"),
+            "{outdated_lock}"
+        );
+        let variables = variables.unwrap();
+        assert_eq!(variables.get("parry2d_version"), Some("0.17.1"));
+        assert_eq!(variables.get("parry2d_features"), Some("\"simd-stable\""));
+        assert_eq!(variables.get("parry_version"), Some("0.17.1"));
+    }
+}
@@ -0,0 +1,165 @@
+//! Regeneration of the outputs of a directory when their templates or loaded files change.
+
+use crate::directory::{collect_templates, inject_templates};
+use crate::outputs::module_files;
+use crate::tags::load_tags;
+use crate::variables::{has_placeholders, Variables};
+use crate::{resolve_path, template_root, Config};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// How often the watched files are polled for modifications.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Injects every template under `input_dir`, then keeps regenerating the outputs whose
+/// template or loaded files change.
+///
+/// Only the outputs affected by a modification are regenerated, and their report is printed
+/// in `config.format`. This never returns.
+pub fn watch_directory(input_dir: &Path, output_dir: &Path, config: &Config) -> ! {
+    let mut watcher = Watcher::default();
+    loop {
+        watcher.poll(input_dir, output_dir, config);
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The files the templates of a directory were last injected from.
+#[derive(Default)]
+struct Watcher {
+    /// Last known modification time of every template and loaded file.
+    mtimes: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl Watcher {
+    /// Regenerates the outputs whose template or loaded files changed since the last poll,
+    /// and returns their templates.
+    ///
+    /// The loaded files include the modules of the examples, whose output may depend on them.
+    /// Files seen for the first time count as modified.
+    fn poll(&mut self, input_dir: &Path, output_dir: &Path, config: &Config) -> Vec<PathBuf> {
+        let templates = match collect_templates(input_dir) {
+            Ok(templates) => templates,
+            Err(diagnostic) => {
+                diagnostic.emit(config.format);
+                Vec::new()
+            }
+        };
+        let mut dependencies = Vec::new();
+        let mut new_mtimes = HashMap::new();
+
+        for template in &templates {
+            let mut template_dependencies = vec![template.clone()];
+            let content = fs::read_to_string(template);
+            if let (Ok(content), Ok(root)) = (content, template_root(template, config)) {
+                for tag in load_tags(&content, config) {
+                    let path = resolve_path(&root, &tag.path);
+                    template_dependencies.extend(module_files(&path));
+                    template_dependencies.push(path);
+                }
+                if has_placeholders(&content) {
+                    match Variables::load(&root) {
+                        Ok(variables) => {
+                            template_dependencies.extend_from_slice(variables.sources())
+                        }
+                        // Regenerate the outputs once the lock is restored.
+                        Err(_) => template_dependencies.push(root.join("Cargo.lock")),
+                    }
+                }
+            }
+            for dependency in &template_dependencies {
+                let mtime = fs::metadata(dependency)
+                    .and_then(|metadata| metadata.modified())
+                    .ok();
+                new_mtimes.insert(dependency.clone(), mtime);
+            }
+            dependencies.push(template_dependencies);
+        }
+
+        let affected = templates
+            .into_iter()
+            .zip(dependencies)
+            .filter(|(_, template_dependencies)| {
+                template_dependencies
+                    .iter()
+                    .any(|dependency| self.mtimes.get(dependency) != new_mtimes.get(dependency))
+            })
+            .map(|(template, _)| template)
+            .collect::<Vec<_>>();
+        self.mtimes = new_mtimes;
+
+        if !affected.is_empty() {
+            inject_templates(input_dir, output_dir, &affected, config, None)
+                .report()
+                .print(config.format);
+        }
+        affected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TempDir;
+    use std::fs::File;
+
+    /// Sets the modification time of the file at `path` to `seconds` after the epoch, so that
+    /// a modification is seen however fast the test runs.
+    fn touch(path: &Path, seconds: u64) {
+        let time = SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn outputs_are_regenerated_when_a_loaded_module_changes() {
+        let dir = TempDir::new("watch_modules");
+        let template = dir.write(
+            "templates/page.mdx",
+            "<load path='/example.rs' marker='a' />\n",
+        );
+        dir.write(
+            "templates/other.mdx",
+            "<load path='/other.rs' marker='a' />\n",
+        );
+        let example =
+            "mod helper;\n// DOCUSAURUS: a start\nhelper::run();\n// DOCUSAURUS: a stop\n";
+        dir.write("example.rs", example);
+        dir.write(
+            "other.rs",
+            "// DOCUSAURUS: a start\n// DOCUSAURUS: a stop\n",
+        );
+        let helper = dir.write("helper/mod.rs", "mod nested;\npub fn run() {}\n");
+        let nested = dir.write("helper/nested.rs", "");
+        let config = Config {
+            root: Some(dir.path().to_path_buf()),
+            ..Config::default()
+        };
+        let (input_dir, output_dir) = (dir.path().join("templates"), dir.path().join("outputs"));
+        let output = output_dir.join("page.mdx");
+
+        let mut watcher = Watcher::default();
+        assert_eq!(watcher.poll(&input_dir, &output_dir, &config).len(), 2);
+        assert_eq!(fs::read_to_string(&output).unwrap(), "helper::run();\n");
+        assert!(watcher.poll(&input_dir, &output_dir, &config).is_empty());
+
+        for (module, seconds) in [(&helper, 1), (&nested, 2)] {
+            fs::remove_file(&output).unwrap();
+            touch(module, seconds);
+            assert_eq!(
+                watcher.poll(&input_dir, &output_dir, &config),
+                [template.as_path()]
+            );
+            assert!(output.is_file());
+        }
+    }
+}
@@ -0,0 +1,2 @@
+//! Only holds `tests/examples.rs`, which runs every example of the documentation with
+//! `inject_file::examples::check_examples`.
@@ -0,0 +1,15 @@
+//! Runs every example of the documentation, so that broken assertions are not only found by
+//! running them by hand.
+
+use inject_file::{examples::check_examples, Format};
+use std::path::Path;
+
+#[test]
+fn every_example_runs() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let report = check_examples(root);
+    assert!(
+        report.print(Format::Human),
+        "some examples failed, see the errors above"
+    );
+}
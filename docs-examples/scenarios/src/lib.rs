@@ -0,0 +1,9 @@
+//! The shapes and positions of the examples that the `illustrations` crate draws.
+//!
+//! The examples assert, in hidden lines, that their snippets use these shapes and positions,
+//! and the images of the guide are drawn from them, so that an image cannot drift from the
+//! code it illustrates. The `scenarios-f64` package builds the same sources against the `f64`
+//! crates, for the `f64` variants of the examples.
+
+pub mod queries;
+pub mod representations;
@@ -0,0 +1,45 @@
+//! The scenarios of `2d/rust/examples/geometric_queries2.rs`, drawn by the figures.
+
+use parry2d::math::{Isometry, Point, Real, Vector};
+use parry2d::query::Ray;
+use parry2d::shape::{Ball, Cuboid};
+
+/// The margin of the `closest_points` region.
+pub const MARGIN: Real = 1.0;
+
+/// The cuboid of the `closest_points`, `contact` and `cast_shapes` regions, at the origin.
+pub fn cuboid() -> Cuboid {
+    Cuboid::new(Vector::new(1.0, 1.0))
+}
+
+/// The ball of the `closest_points`, `contact` and `cast_shapes` regions.
+pub fn ball() -> Ball {
+    Ball::new(1.0)
+}
+
+/// The positions of the ball: intersecting the cuboid, within [`MARGIN`] of it, and farther.
+pub fn ball_positions() -> [Isometry<Real>; 3] {
+    [1.0, 2.0, 3.0].map(|offset| Isometry::translation(offset, offset))
+}
+
+/// The velocities of the ball and of the cuboid in the `cast_shapes` region, from each of the
+/// [`ball_positions`]: touching at time 0, touching later, and never touching.
+pub fn velocities() -> [(Vector<Real>, Vector<Real>); 3] {
+    let (ball_vel1, box_vel1) = (Vector::new(2.0, 2.0), Vector::new(-1.0, 1.0));
+    let (ball_vel2, box_vel2) = (Vector::new(-0.5, -0.5), Vector::new(1.0, 1.0));
+    [
+        (ball_vel1, box_vel1),
+        (ball_vel2, box_vel2),
+        (ball_vel1, box_vel1),
+    ]
+}
+
+/// The cuboid of the `ray_cast` region, at the origin.
+pub fn ray_cast_cuboid() -> Cuboid {
+    Cuboid::new(Vector::new(1.0, 2.0))
+}
+
+/// The ray of the `ray_cast` region starting inside of the [`ray_cast_cuboid`].
+pub fn ray_inside() -> Ray {
+    Ray::new(Point::origin(), Vector::y())
+}
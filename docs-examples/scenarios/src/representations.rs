@@ -0,0 +1,87 @@
+//! The shapes of `3d/rust/examples/geometric_representations3.rs`, rendered by the gallery.
+
+use parry3d::math::{Isometry, Point, Real, Vector};
+use parry3d::shape::{
+    Ball, Capsule, Compound, Cone, ConvexPolyhedron, Cuboid, Cylinder, SharedShape, TriMesh,
+};
+
+/// The shape of the `ball` region.
+pub fn ball() -> Ball {
+    Ball::new(1.0)
+}
+
+/// The shape of the `cuboid` region.
+pub fn cuboid() -> Cuboid {
+    Cuboid::new(Vector::new(2.0, 1.0, 3.0))
+}
+
+/// The shape of the `cylinder` region.
+pub fn cylinder() -> Cylinder {
+    Cylinder::new(0.5, 1.0)
+}
+
+/// The shape of the `cone` region.
+pub fn cone() -> Cone {
+    Cone::new(0.5, 0.75)
+}
+
+/// The shape of the `capsule` region.
+pub fn capsule() -> Capsule {
+    Capsule::new(Point::new(0.0, 0.5, 0.0), Point::new(0.0, 1.0, 0.0), 0.75)
+}
+
+/// The shape of the `ConvexPolyhedron` region, the convex hull of an octahedron and its center.
+pub fn convex() -> ConvexPolyhedron {
+    let points = [
+        Point::new(0.0, 0.0, 1.0),
+        Point::new(0.0, 0.0, -1.0),
+        Point::new(0.0, 1.0, 0.0),
+        Point::new(0.0, -1.0, 0.0),
+        Point::new(1.0, 0.0, 0.0),
+        Point::new(-1.0, 0.0, 0.0),
+        Point::new(0.0, 0.0, 0.0),
+    ];
+    ConvexPolyhedron::from_convex_hull(&points).expect("Convex hull computation failed.")
+}
+
+/// The shape of the `compound` region, three boxes shaped like a `U`.
+pub fn compound() -> Compound {
+    let horizontal_box = Cuboid::new(Vector::new(1.5, 0.25, 0.25));
+    let vertical_box = Cuboid::new(Vector::new(0.25, 1.5, 0.25));
+    let parts = [
+        (Vector::new(0.0, -1.5, 0.0), horizontal_box),
+        (Vector::new(-1.5, 0.0, 0.0), vertical_box),
+        (Vector::new(1.5, 0.0, 0.0), vertical_box),
+    ];
+    Compound::new(
+        parts
+            .into_iter()
+            .map(|(delta, cuboid)| {
+                (
+                    Isometry::new(delta, Vector::zeros()),
+                    SharedShape::new(cuboid),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// The position and shape of each part of `compound` that is a cuboid.
+pub fn cuboids(compound: &Compound) -> Vec<(Isometry<Real>, Cuboid)> {
+    compound
+        .shapes()
+        .iter()
+        .filter_map(|(pos, part)| Some((*pos, *part.as_cuboid()?)))
+        .collect()
+}
+
+/// The shape of the `trimesh` region.
+pub fn trimesh() -> TriMesh {
+    let points = vec![
+        Point::new(0.0, 1.0, 0.0),
+        Point::new(-1.0, -0.5, 0.0),
+        Point::new(0.0, -0.5, -1.0),
+        Point::new(1.0, -0.5, 0.0),
+    ];
+    TriMesh::new(points, vec![[0, 1, 2], [0, 2, 3], [0, 3, 1]])
+}
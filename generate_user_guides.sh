@@ -13,4 +13,10 @@ fi
 
 mkdir -p docs/user_guide/rust/
 
-find docs/user_guide/templates/ -type f -print0 | OUTPUT_FOLDER=docs/user_guide xargs -0 ./inject_code_in_user_guide.sh
+# Print the changes of every regenerated guide.
+PRINT_DIFF="${PRINT_DIFF:-0}"
+if [ "$PRINT_DIFF" -eq 1 ]; then
+    set -- --print-diff "$@"
+fi
+
+cargo run --quiet --release --manifest-path docs-examples/inject_file/Cargo.toml -- "$@" docs/user_guide/templates docs/user_guide
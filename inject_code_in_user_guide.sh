@@ -20,23 +20,20 @@ if [ ! -d $OUTPUT_FOLDER ]; then
     exit;
 fi
 
-cd docs-examples/inject_file
-OUTPUT_FOLDER="../../$OUTPUT_FOLDER"
-
-
-mkdir -p "tmp_diff"
+TMP_DIFF="docs-examples/inject_file/tmp_diff"
+mkdir -p "$TMP_DIFF"
 
 error=0
 for path in "$@"
 do
-    file_to_inject="../../$path"
-    diff_file="tmp_diff/$path"
+    file_to_inject="$path"
+    diff_file="$TMP_DIFF/$path"
 
     filename=$(basename -- "$file_to_inject")
     file_injected="$OUTPUT_FOLDER/$filename"
     echo $file_to_inject
     
-    cargo run --quiet --release "$file_to_inject" > "$file_injected"
+    cargo run --quiet --release --manifest-path docs-examples/inject_file/Cargo.toml -- "$file_to_inject" > "$file_injected"
     if [ $? -eq 1 ]; then
         error=1
     fi
@@ -52,7 +49,7 @@ do
 done
 
 if [ ! $KEEP_TMP ]; then
-    rm -rf tmp_diff
+    rm -rf "$TMP_DIFF"
 fi
 
 exit $error
\ No newline at end of file
@@ -16,6 +16,7 @@ module.exports = {
         user_guide_root + 'bounding_volumes',
         user_guide_root + 'geometric_queries',
         user_guide_root + 'mesh_transformation',
+        user_guide_root + 'snippets',
       ],
     },
     {